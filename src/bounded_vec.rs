@@ -0,0 +1,296 @@
+//! [`BoundedVec`] fixed capacity list type used by the [`Bounded`](crate::Bounded) marker
+
+use core::borrow::{Borrow, BorrowMut};
+use core::fmt::Debug;
+use core::hash::{Hash, Hasher};
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ops::{Deref, DerefMut};
+
+use crate::CapacityError;
+
+/// Fixed capacity list holding up to `N` items inline, with an explicit length
+///
+/// ```
+/// use stor::BoundedVec;
+///
+/// // Construct statics at compile time
+/// static ITEMS: BoundedVec<u16, 4> = BoundedVec::from_slice_const(&[1, 2]);
+/// assert_eq!(ITEMS, [1, 2]);
+///
+/// // Or at runtime, failing where the capacity is exceeded
+/// let mut v = BoundedVec::<u16, 2>::new();
+/// v.push(1).unwrap();
+/// v.push(2).unwrap();
+/// assert!(v.push(3).is_err());
+/// ```
+pub struct BoundedVec<T, const N: usize> {
+    buff: [MaybeUninit<T>; N],
+    len: usize,
+}
+
+impl <T, const N: usize> BoundedVec<T, N> {
+    /// Create a new empty [`BoundedVec`]
+    pub const fn new() -> Self {
+        Self { buff: [const { MaybeUninit::uninit() }; N], len: 0 }
+    }
+
+    /// Create a [`BoundedVec`] by copying items from a slice, returning [`CapacityError`] if these do not fit
+    pub const fn from_slice(s: &[T]) -> Result<Self, CapacityError> where T: Copy {
+        if s.len() > N {
+            return Err(CapacityError);
+        }
+
+        Ok(Self::from_slice_const(s))
+    }
+
+    /// Create a [`BoundedVec`] by copying items from a slice, for use in `const` and `static` items
+    ///
+    /// # Panics
+    /// If the items do not fit, which is a compile time error in const contexts
+    pub const fn from_slice_const(s: &[T]) -> Self where T: Copy {
+        assert!(s.len() <= N, "slice exceeds BoundedVec capacity");
+
+        let mut v = Self::new();
+        while v.len < s.len() {
+            v.buff[v.len] = MaybeUninit::new(s[v.len]);
+            v.len += 1;
+        }
+
+        v
+    }
+
+    /// Fetch the list contents as a slice
+    pub const fn as_slice(&self) -> &[T] {
+        // SAFETY: the first `len` items are always initialised
+        unsafe { core::slice::from_raw_parts(self.buff.as_ptr() as *const T, self.len) }
+    }
+
+    /// Fetch the list contents as a mutable slice
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: the first `len` items are always initialised
+        unsafe { core::slice::from_raw_parts_mut(self.buff.as_mut_ptr() as *mut T, self.len) }
+    }
+
+    /// Fetch the number of items in the list
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check whether the list is empty
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Check whether the list is full
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Fetch the list capacity
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Append an item, returning it in `Err` if the list is full
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        if self.len == N {
+            return Err(value);
+        }
+
+        self.buff[self.len] = MaybeUninit::new(value);
+        self.len += 1;
+
+        Ok(())
+    }
+
+    /// Remove and return the last item, if any
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        // SAFETY: item was initialised and is no longer tracked by `len`
+        Some(unsafe { self.buff[self.len].assume_init_read() })
+    }
+
+    /// Append cloned items from a slice, returning [`CapacityError`] and leaving
+    /// the list unchanged if these do not fit
+    pub fn extend_from_slice(&mut self, s: &[T]) -> Result<(), CapacityError> where T: Clone {
+        if self.len + s.len() > N {
+            return Err(CapacityError);
+        }
+
+        for v in s {
+            self.buff[self.len] = MaybeUninit::new(v.clone());
+            self.len += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Shorten the list to the provided length, dropping any remaining items
+    pub fn truncate(&mut self, len: usize) {
+        while self.len > len {
+            self.len -= 1;
+            // SAFETY: item was initialised and is no longer tracked by `len`
+            unsafe { self.buff[self.len].assume_init_drop() };
+        }
+    }
+
+    /// Remove all items from the list
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+
+    /// Convert into an array where the list is full, returning the list otherwise
+    pub fn into_array(self) -> Result<[T; N], Self> {
+        if self.len != N {
+            return Err(self);
+        }
+
+        let v = ManuallyDrop::new(self);
+        // SAFETY: all `N` items are initialised, and ownership moves to the array as `v` is not dropped
+        Ok(unsafe { core::ptr::read(v.buff.as_ptr() as *const [T; N]) })
+    }
+}
+
+impl <T, const N: usize> Drop for BoundedVec<T, N> {
+    fn drop(&mut self) {
+        // SAFETY: the first `len` items are always initialised
+        unsafe { core::ptr::drop_in_place(self.as_mut_slice()) }
+    }
+}
+
+impl <T: Clone, const N: usize> Clone for BoundedVec<T, N> {
+    fn clone(&self) -> Self {
+        let mut v = Self::new();
+        for i in self.as_slice() {
+            v.buff[v.len] = MaybeUninit::new(i.clone());
+            v.len += 1;
+        }
+        v
+    }
+}
+
+impl <T, const N: usize> Default for BoundedVec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <T: Debug, const N: usize> Debug for BoundedVec<T, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl <T, const N: usize> Deref for BoundedVec<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl <T, const N: usize> DerefMut for BoundedVec<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl <T, const N: usize> AsRef<[T]> for BoundedVec<T, N> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl <T, const N: usize> AsMut<[T]> for BoundedVec<T, N> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl <T, const N: usize> Borrow<[T]> for BoundedVec<T, N> {
+    fn borrow(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl <T, const N: usize> BorrowMut<[T]> for BoundedVec<T, N> {
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl <T, const N: usize> From<[T; N]> for BoundedVec<T, N> {
+    fn from(a: [T; N]) -> Self {
+        Self { buff: a.map(MaybeUninit::new), len: N }
+    }
+}
+
+impl <'a, T: Clone, const N: usize> TryFrom<&'a [T]> for BoundedVec<T, N> {
+    type Error = CapacityError;
+
+    fn try_from(s: &'a [T]) -> Result<Self, Self::Error> {
+        let mut v = Self::new();
+        v.extend_from_slice(s)?;
+        Ok(v)
+    }
+}
+
+impl <'a, T, const N: usize> IntoIterator for &'a BoundedVec<T, N> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl <'a, T, const N: usize> IntoIterator for &'a mut BoundedVec<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_mut_slice().iter_mut()
+    }
+}
+
+impl <T: PartialEq, const N: usize, const M: usize> PartialEq<BoundedVec<T, M>> for BoundedVec<T, N> {
+    fn eq(&self, other: &BoundedVec<T, M>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl <T: Eq, const N: usize> Eq for BoundedVec<T, N> {}
+
+impl <T: PartialEq, const N: usize> PartialEq<[T]> for BoundedVec<T, N> {
+    fn eq(&self, other: &[T]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl <T: PartialEq, const N: usize, const M: usize> PartialEq<[T; M]> for BoundedVec<T, N> {
+    fn eq(&self, other: &[T; M]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl <T: PartialOrd, const N: usize> PartialOrd for BoundedVec<T, N> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl <T: Ord, const N: usize> Ord for BoundedVec<T, N> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl <T: Hash, const N: usize> Hash for BoundedVec<T, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
@@ -0,0 +1,135 @@
+//! Bound-collecting traits for deriving standard traits on [`Stor`] generic types
+//!
+//! `#[derive(..)]` adds a bound for each field of an associated type, so deriving
+//! `Clone`, `PartialEq`, `Eq`, `Hash`, `PartialOrd` or `Ord` on a type generic over
+//! `S: Stor` works for any marker. Generic code using these impls may then bound
+//! `S` by one of these traits in place of each associated type.
+//!
+//! Lists implement these traits for all markers where their items do, however
+//! as this cannot be expressed for all `T` in a bound, generic code using lists
+//! must also bound `S::List<T>` for the items in use.
+
+use core::borrow::Borrow;
+use core::hash::Hash;
+
+use crate::Stor;
+
+/// [`StorClone`] bounds [`Stor`] string and byte containers by [`Clone`]
+pub trait StorClone: Stor<String: Clone, Bytes: Clone> + Clone {}
+
+impl <S: Stor<String: Clone, Bytes: Clone> + Clone> StorClone for S {}
+
+/// [`StorEq`] bounds [`Stor`] string and byte containers by [`Eq`]
+///
+/// ```
+/// use stor::{Stor, StorEq, StorHash, Owned, Ref};
+///
+/// #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// struct Header<S: Stor> {
+///     name: S::String,
+///     value: S::Bytes,
+/// }
+///
+/// fn same<S: StorEq>(a: &Header<S>, b: &Header<S>) -> bool {
+///     a == b
+/// }
+///
+/// fn index<S: StorHash + StorEq>(h: Header<S>) -> std::collections::HashSet<Header<S>> {
+///     std::collections::HashSet::from([h])
+/// }
+///
+/// let h = Header::<Ref>{ name: "a", value: &[1] };
+/// assert!(same(&h, &h.clone()));
+/// assert_eq!(index(Header::<Owned>{ name: "a".to_string(), value: vec![1] }).len(), 1);
+/// ```
+pub trait StorEq: Stor<String: Eq, Bytes: Eq> + Eq {}
+
+impl <S: Stor<String: Eq, Bytes: Eq> + Eq> StorEq for S {}
+
+/// [`StorHash`] bounds [`Stor`] string and byte containers by [`Hash`]
+pub trait StorHash: Stor<String: Hash, Bytes: Hash> + Hash {}
+
+impl <S: Stor<String: Hash, Bytes: Hash> + Hash> StorHash for S {}
+
+/// [`StorOrd`] bounds [`Stor`] string and byte containers by [`Ord`]
+pub trait StorOrd: StorEq + Stor<String: Ord, Bytes: Ord> + Ord {}
+
+impl <S: StorEq + Stor<String: Ord, Bytes: Ord> + Ord> StorOrd for S {}
+
+/// [`StorBorrow`] bounds [`Stor`] string and byte containers by [`Borrow<str>`](Borrow) and
+/// [`Borrow<[u8]>`](Borrow), with [`Eq`], [`Ord`] and [`Hash`] consistent with the borrowed form
+///
+/// This allows generic maps and sets keyed on `S::String` or `S::Bytes` to be queried
+/// by `&str` or `&[u8]`. Unlike other bound traits this is implemented for each marker
+/// rather than inferred, as consistency cannot be expressed as a bound. `HeaplessSized`
+/// does not implement this as `heapless` containers do not implement [`Borrow`],
+/// [`Bounded`](crate::Bounded) provides an alternative without allocation.
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use stor::{Stor, StorBorrow, Owned, Bounded};
+///
+/// fn lookup<S: StorBorrow>(index: &BTreeMap<S::String, u32>, name: &str) -> Option<u32> {
+///     index.get(name).copied()
+/// }
+///
+/// let mut a = BTreeMap::<<Owned as Stor>::String, u32>::new();
+/// a.insert("a".to_string(), 1);
+/// assert_eq!(lookup::<Owned>(&a, "a"), Some(1));
+///
+/// let mut b = BTreeMap::<<Bounded<4> as Stor>::String, u32>::new();
+/// b.insert("b".try_into().unwrap(), 2);
+/// assert_eq!(lookup::<Bounded<4>>(&b, "b"), Some(2));
+/// ```
+pub trait StorBorrow: StorOrd + StorHash + Stor<String: Borrow<str>, Bytes: Borrow<[u8]>> {}
+
+#[cfg(feature = "alloc")]
+impl StorBorrow for crate::Owned {}
+
+#[cfg(feature = "alloc")]
+impl StorBorrow for crate::Boxed {}
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl StorBorrow for crate::Shared {}
+
+#[cfg(feature = "alloc")]
+impl StorBorrow for crate::Local {}
+
+#[cfg(feature = "alloc")]
+impl <'a> StorBorrow for crate::Cow<'a> {}
+
+#[cfg(feature = "allocator_api")]
+impl <A: core::alloc::Allocator> StorBorrow for crate::OwnedIn<A> {}
+
+#[cfg(feature = "bumpalo")]
+impl <'a> StorBorrow for crate::Bump<'a> {}
+
+#[cfg(feature = "smallvec")]
+impl <const N: usize> StorBorrow for crate::Inline<N> {}
+
+#[cfg(all(feature = "bytes", target_has_atomic = "ptr"))]
+impl StorBorrow for crate::Buf {}
+
+#[cfg(feature = "smol_str")]
+impl StorBorrow for crate::Smol {}
+
+#[cfg(feature = "compact_str")]
+impl StorBorrow for crate::Compact {}
+
+impl <'a> StorBorrow for crate::Ref<'a> {}
+
+impl <const N: usize> StorBorrow for crate::Const<N> {}
+
+impl <const N: usize> StorBorrow for crate::Bounded<N> {}
+
+#[cfg(feature = "arrayvec")]
+impl <const L: usize, const S: usize, const B: usize> StorBorrow for crate::ArrayVecSized<L, S, B> {}
+
+#[cfg(feature = "tinyvec")]
+impl <const L: usize, const S: usize, const B: usize> StorBorrow for crate::TinyVecSized<L, S, B> {}
+
+#[cfg(feature = "slab")]
+impl <'a> StorBorrow for crate::Slab<'a> {}
+
+#[cfg(all(feature = "pool", target_has_atomic = "8"))]
+impl <'a, const B: usize> StorBorrow for crate::Pooled<'a, B> {}
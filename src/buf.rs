@@ -0,0 +1,289 @@
+//! [`Buf`] marker for zero-copy shared storage using [`bytes`] (requires `bytes` feature)
+
+use core::borrow::Borrow;
+use core::fmt::{Debug, Display};
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use bytes::Bytes;
+
+use crate::codec::{CodecError, Decode, Decoder, StorDecode};
+use crate::{FromOwned, IntoOwned, Stor, StorError, TryFromStor};
+
+/// Buf marker uses [`Bytes`] and [`BufString`] containers sharing reference-counted buffers,
+/// with [`Arc`] for lists
+///
+/// Strings and bytes borrowed from a parent [`Bytes`] buffer are converted without copying
+/// using [`Buf::string_in`] and [`Buf::bytes_in`], and with the `derive` feature
+/// `#[derive(StorConvert)]` generates `to_buf_stor()` to convert an entire object.
+/// Conversion from [`Owned`](crate::Owned) via [`FromOwned`] also keeps existing allocations.
+///
+/// ```
+/// use bytes::Bytes;
+/// use stor::{Stor, Buf, Ref};
+///
+/// #[derive(Debug)]
+/// struct Header<S: Stor> {
+///     name: S::String,
+///     value: S::Bytes,
+/// }
+///
+/// let parent = Bytes::from_static(b"abc\xaa\xbb");
+///
+/// // Parse a borrowed header from the buffer
+/// let r = Header::<Ref> {
+///     name: core::str::from_utf8(&parent[..3]).unwrap(),
+///     value: &parent[3..],
+/// };
+///
+/// // Convert to shared storage without copying
+/// let h = Header::<Buf> {
+///     name: Buf::string_in(r.name, &parent),
+///     value: Buf::bytes_in(r.value, &parent),
+/// };
+/// assert_eq!(h.name, "abc");
+/// assert_eq!(h.value.as_ptr(), parent[3..].as_ptr());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Buf;
+
+impl Stor for Buf {
+    type List<T: Debug> = Arc<[T]>;
+    type String = BufString;
+    type Bytes = Bytes;
+}
+
+impl Buf {
+    /// Slice bytes from the parent buffer where these are borrowed from it, otherwise copy them
+    pub fn bytes_in(v: &[u8], parent: &Bytes) -> Bytes {
+        if is_within(v, parent) {
+            parent.slice_ref(v)
+        } else {
+            Bytes::copy_from_slice(v)
+        }
+    }
+
+    /// Slice a string from the parent buffer where this is borrowed from it, otherwise copy it
+    pub fn string_in(v: &str, parent: &Bytes) -> BufString {
+        BufString { buff: Self::bytes_in(v.as_bytes(), parent) }
+    }
+}
+
+/// Check whether a slice lies within the parent buffer
+fn is_within(v: &[u8], parent: &Bytes) -> bool {
+    let p = parent.as_ptr_range();
+    let v = v.as_ptr_range();
+    !parent.is_empty() && p.start <= v.start && v.end <= p.end
+}
+
+/// UTF-8 string sharing a [`Bytes`] buffer, used by the [`Buf`] marker
+#[derive(Clone, Default)]
+pub struct BufString {
+    buff: Bytes,
+}
+
+impl BufString {
+    /// Create a new empty [`BufString`]
+    pub const fn new() -> Self {
+        Self { buff: Bytes::new() }
+    }
+
+    /// Create a [`BufString`] from a static string without copying
+    pub const fn from_static(s: &'static str) -> Self {
+        Self { buff: Bytes::from_static(s.as_bytes()) }
+    }
+
+    /// Create a [`BufString`] from UTF-8 bytes, returning the bytes if these are not valid
+    pub fn from_utf8(buff: Bytes) -> Result<Self, Bytes> {
+        match core::str::from_utf8(&buff) {
+            Ok(_) => Ok(Self { buff }),
+            Err(_) => Err(buff),
+        }
+    }
+
+    /// Fetch the string contents
+    pub fn as_str(&self) -> &str {
+        // SAFETY: contents are validated as UTF-8 on construction
+        unsafe { core::str::from_utf8_unchecked(&self.buff) }
+    }
+
+    /// Fetch the string contents as bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buff
+    }
+
+    /// Convert into the underlying [`Bytes`] buffer
+    pub fn into_bytes(self) -> Bytes {
+        self.buff
+    }
+
+    /// Fetch the string length in bytes
+    pub fn len(&self) -> usize {
+        self.buff.len()
+    }
+
+    /// Check whether the string is empty
+    pub fn is_empty(&self) -> bool {
+        self.buff.is_empty()
+    }
+}
+
+impl From<&str> for BufString {
+    fn from(s: &str) -> Self {
+        Self { buff: Bytes::copy_from_slice(s.as_bytes()) }
+    }
+}
+
+/// Conversion from [`String`] keeps the existing allocation
+impl From<String> for BufString {
+    fn from(s: String) -> Self {
+        Self { buff: Bytes::from(s) }
+    }
+}
+
+impl From<BufString> for Bytes {
+    fn from(s: BufString) -> Self {
+        s.buff
+    }
+}
+
+impl TryFrom<Bytes> for BufString {
+    type Error = StorError;
+
+    fn try_from(buff: Bytes) -> Result<Self, Self::Error> {
+        Self::from_utf8(buff).map_err(|_| StorError::InvalidUtf8)
+    }
+}
+
+impl Deref for BufString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for BufString {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<[u8]> for BufString {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl Borrow<str> for BufString {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Debug for BufString {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl Display for BufString {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+impl PartialEq for BufString {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for BufString {}
+
+impl PartialEq<str> for BufString {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl <'a> PartialEq<&'a str> for BufString {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialOrd for BufString {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BufString {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl Hash for BufString {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+/// Conversion into owned storage avoids a copy where buffers are not shared
+impl IntoOwned for Buf {
+    fn into_owned_list<T: Debug + Clone>(list: Self::List<T>) -> Vec<T> {
+        list.to_vec()
+    }
+
+    fn into_owned_string(string: Self::String) -> String {
+        // SAFETY: contents are validated as UTF-8 on construction
+        unsafe { String::from_utf8_unchecked(Vec::from(string.buff)) }
+    }
+
+    fn into_owned_bytes(bytes: Self::Bytes) -> Vec<u8> {
+        Vec::from(bytes)
+    }
+}
+
+/// Conversion from owned storage keeps existing string and byte allocations
+impl FromOwned for Buf {
+    fn from_owned_list<T: Debug>(list: Vec<T>) -> Self::List<T> {
+        list.into()
+    }
+
+    fn from_owned_string(string: String) -> Self::String {
+        BufString::from(string)
+    }
+
+    fn from_owned_bytes(bytes: Vec<u8>) -> Self::Bytes {
+        Bytes::from(bytes)
+    }
+}
+
+impl <'a> TryFromStor<'a> for Buf {
+    fn try_list_from<T: Debug + Clone>(v: &'a [T]) -> Result<Self::List<T>, StorError> {
+        Ok(Arc::from(v))
+    }
+
+    fn try_string_from(v: &'a str) -> Result<Self::String, StorError> {
+        Ok(BufString::from(v))
+    }
+
+    fn try_bytes_from(v: &'a [u8]) -> Result<Self::Bytes, StorError> {
+        Ok(Bytes::copy_from_slice(v))
+    }
+}
+
+impl <'a> StorDecode<'a> for Buf {
+    fn decode_list<T: Debug + Decode<'a>>(d: &mut Decoder<'a>) -> Result<Self::List<T>, CodecError> {
+        crate::Owned::decode_list(d).map(|l| l.into())
+    }
+}
@@ -0,0 +1,275 @@
+//! [`Bump`] marker for arena-allocated storage using [`bumpalo`] (requires `bumpalo` feature)
+
+use core::alloc::Layout;
+use core::borrow::{Borrow, BorrowMut};
+use core::fmt::Debug;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+
+use bumpalo::collections::{String, Vec};
+
+use crate::Stor;
+
+/// Bump marker uses containers allocated from a caller-provided [`bumpalo::Bump`] arena,
+/// for cheap bulk allocation and bulk free of short-lived data
+///
+/// Containers are created with the arena, [`Bump::list_in`], [`Bump::string_in`] and
+/// [`Bump::bytes_in`] copy borrowed data into the arena, and with the `derive` feature
+/// `#[derive(StorConvert)]` generates `to_bump_stor()` to copy an entire object.
+/// As containers require an arena, [`TryFromStor`](crate::TryFromStor) and
+/// [`StorMut`](crate::StorMut) are not implemented.
+///
+/// With the `serde` feature [`BumpList`] serialises as a sequence, however cannot be
+/// deserialised without an arena so always returns an error at runtime.
+///
+/// ```
+/// use stor::{Stor, Bump, RefList};
+///
+/// #[derive(Debug)]
+/// struct Record<S: Stor> {
+///     tags: S::List<u16>,
+///     name: S::String,
+/// }
+///
+/// let arena = bumpalo::Bump::new();
+///
+/// let r = Record::<stor::Ref> { tags: RefList::new(&[1, 2]), name: "abc" };
+/// let b = Record::<Bump> {
+///     tags: Bump::list_in(&r.tags, &arena),
+///     name: Bump::string_in(r.name, &arena),
+/// };
+/// assert_eq!(b.tags, [1, 2]);
+/// assert_eq!(b.name, "abc");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Bump<'a> (PhantomData<&'a ()>);
+
+impl <'a> Stor for Bump<'a> {
+    type List<T: Debug> = BumpList<'a, T>;
+    type String = String<'a>;
+    type Bytes = Vec<'a, u8>;
+}
+
+impl <'a> Bump<'a> {
+    /// Copy a list into the arena
+    pub fn list_in<T: Clone>(v: &[T], bump: &'a bumpalo::Bump) -> BumpList<'a, T> {
+        BumpList::from_slice_in(v, bump)
+    }
+
+    /// Copy a string into the arena
+    pub fn string_in(v: &str, bump: &'a bumpalo::Bump) -> String<'a> {
+        String::from_str_in(v, bump)
+    }
+
+    /// Copy bytes into the arena
+    pub fn bytes_in(v: &[u8], bump: &'a bumpalo::Bump) -> Vec<'a, u8> {
+        let mut b = Vec::with_capacity_in(v.len(), bump);
+        b.extend_from_slice(v);
+        b
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl <'a> crate::IntoOwned for Bump<'a> {
+    fn into_owned_list<T: Debug + Clone>(list: Self::List<T>) -> alloc::vec::Vec<T> {
+        list.as_slice().to_vec()
+    }
+
+    fn into_owned_string(string: Self::String) -> alloc::string::String {
+        alloc::string::String::from(string.as_str())
+    }
+
+    fn into_owned_bytes(bytes: Self::Bytes) -> alloc::vec::Vec<u8> {
+        bytes.to_vec()
+    }
+}
+
+/// Fixed length list allocated from a [`bumpalo::Bump`] arena, used by the [`Bump`] marker
+///
+/// The [`Bump`] marker uses this in place of [`bumpalo::collections::Vec`] as the generic
+/// `List<T>` type cannot require `T: 'a`. Unlike values allocated directly in the arena,
+/// items are dropped with the list, with the memory released when the arena is reset
+/// or dropped.
+pub struct BumpList<'a, T> {
+    ptr: NonNull<T>,
+    len: usize,
+    bump: &'a bumpalo::Bump,
+    _type: PhantomData<T>,
+}
+
+impl <'a, T> BumpList<'a, T> {
+    /// Create an empty [`BumpList`] in the provided arena
+    pub fn new_in(bump: &'a bumpalo::Bump) -> Self {
+        Self { ptr: NonNull::dangling(), len: 0, bump, _type: PhantomData }
+    }
+
+    /// Create a [`BumpList`] from an iterator of known length, allocating from the provided arena
+    ///
+    /// # Panics
+    /// If the iterator yields fewer items than its reported length, or allocation fails
+    pub fn from_iter_in<I>(iter: I, bump: &'a bumpalo::Bump) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let mut iter = iter.into_iter();
+        let len = iter.len();
+
+        let layout = Layout::array::<T>(len).expect("list exceeds maximum allocation size");
+        let ptr = bump.alloc_layout(layout).cast::<T>();
+
+        let mut l = Self { ptr, len: 0, bump, _type: PhantomData };
+        while l.len < len {
+            let v = iter.next().expect("iterator shorter than reported length");
+            // SAFETY: allocation holds `len` items, and `l.len` only counts initialised items
+            unsafe { l.ptr.as_ptr().add(l.len).write(v) };
+            l.len += 1;
+        }
+
+        l
+    }
+
+    /// Create a [`BumpList`] by cloning items from a slice, allocating from the provided arena
+    pub fn from_slice_in(s: &[T], bump: &'a bumpalo::Bump) -> Self where T: Clone {
+        Self::from_iter_in(s.iter().cloned(), bump)
+    }
+
+    /// Fetch the list contents as a slice
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: the first `len` items are initialised, and live as long as the arena borrow
+        unsafe { core::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Fetch the list contents as a mutable slice
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: the first `len` items are initialised and owned by this list
+        unsafe { core::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Fetch the arena this list is allocated from
+    pub fn bump(&self) -> &'a bumpalo::Bump {
+        self.bump
+    }
+}
+
+impl <'a, T: 'a> From<Vec<'a, T>> for BumpList<'a, T> {
+    fn from(v: Vec<'a, T>) -> Self {
+        let bump = v.bump();
+        let s = v.into_bump_slice_mut();
+        Self {
+            // SAFETY: slice pointers are never null
+            ptr: unsafe { NonNull::new_unchecked(s.as_mut_ptr()) },
+            len: s.len(),
+            bump,
+            _type: PhantomData,
+        }
+    }
+}
+
+impl <'a, T> Drop for BumpList<'a, T> {
+    fn drop(&mut self) {
+        // SAFETY: items are owned by this list, memory is released with the arena
+        unsafe { core::ptr::drop_in_place(self.as_mut_slice()) }
+    }
+}
+
+impl <'a, T: Clone> Clone for BumpList<'a, T> {
+    fn clone(&self) -> Self {
+        Self::from_slice_in(self.as_slice(), self.bump)
+    }
+}
+
+impl <'a, T: Debug> Debug for BumpList<'a, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl <'a, T> Deref for BumpList<'a, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl <'a, T> DerefMut for BumpList<'a, T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl <'a, T> AsRef<[T]> for BumpList<'a, T> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl <'a, T> AsMut<[T]> for BumpList<'a, T> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl <'a, T> Borrow<[T]> for BumpList<'a, T> {
+    fn borrow(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl <'a, T> BorrowMut<[T]> for BumpList<'a, T> {
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl <'a, 'b, T> IntoIterator for &'b BumpList<'a, T> {
+    type Item = &'b T;
+    type IntoIter = core::slice::Iter<'b, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl <'a, 'b, T: PartialEq> PartialEq<BumpList<'b, T>> for BumpList<'a, T> {
+    fn eq(&self, other: &BumpList<'b, T>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl <'a, T: Eq> Eq for BumpList<'a, T> {}
+
+impl <'a, T: PartialEq> PartialEq<[T]> for BumpList<'a, T> {
+    fn eq(&self, other: &[T]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl <'a, T: PartialEq, const N: usize> PartialEq<[T; N]> for BumpList<'a, T> {
+    fn eq(&self, other: &[T; N]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl <'a, T: PartialOrd> PartialOrd for BumpList<'a, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl <'a, T: Ord> Ord for BumpList<'a, T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl <'a, T: Hash> Hash for BumpList<'a, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
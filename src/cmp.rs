@@ -0,0 +1,37 @@
+//! Content-based comparison between containers of different [`Stor`](crate::Stor) markers
+//!
+//! Containers for different markers are distinct types, so do not implement
+//! [`PartialEq`] between each other. These functions compare contents using
+//! the [`AsRef`] bounds required by [`Stor`](crate::Stor), and with the `derive`
+//! feature `#[derive(StorPartialEq)]` implements [`PartialEq`]
+//! between instantiations of a type.
+//!
+//! ```
+//! use stor::{Stor, Owned, Ref, Heapless, RefList, cmp};
+//!
+//! let o: <Owned as Stor>::List<u16> = vec![1, 2];
+//! let r: <Ref as Stor>::List<u16> = RefList::new(&[1, 2]);
+//! assert!(cmp::eq_list(&o, &r));
+//!
+//! let h: <Heapless<4> as Stor>::String = "abc".try_into().unwrap();
+//! assert!(cmp::eq_string(&h, &"abc"));
+//! ```
+
+/// Compare the contents of two string containers
+pub fn eq_string<A: AsRef<str> + ?Sized, B: AsRef<str> + ?Sized>(a: &A, b: &B) -> bool {
+    a.as_ref() == b.as_ref()
+}
+
+/// Compare the contents of two byte containers
+pub fn eq_bytes<A: AsRef<[u8]> + ?Sized, B: AsRef<[u8]> + ?Sized>(a: &A, b: &B) -> bool {
+    a.as_ref() == b.as_ref()
+}
+
+/// Compare the contents of two list containers, with items compared by [`PartialEq`]
+///
+/// Items may differ in type, allowing comparison of lists of objects generic over
+/// different markers where these implement [`PartialEq`] between each other.
+pub fn eq_list<T: PartialEq<U>, U, A: AsRef<[T]> + ?Sized, B: AsRef<[U]> + ?Sized>(a: &A, b: &B) -> bool {
+    let (a, b) = (a.as_ref(), b.as_ref());
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a == b)
+}
@@ -0,0 +1,469 @@
+//! Compact binary codec for [`Stor`](crate::Stor) generic types
+//!
+//! Values are encoded with fixed width little-endian integers, with strings,
+//! bytes and lists prefixed by a LEB128 variable length item count. Enum
+//! variants are encoded as a variable length index followed by their fields.
+//!
+//! Decoding borrows strings and bytes from the input buffer for [`Ref`], checks
+//! capacities for fixed-capacity storage, and copies into allocations for `Owned`.
+//! This requires neither `alloc` nor `serde`, so the same wire types can be shared
+//! between firmware and host tooling.
+//!
+//! ```
+//! use stor::{Stor, Ref, Heapless};
+//! use stor::codec::{self, Encode, Decode, Encoder, Decoder, StorDecode, CodecError};
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct Header<S: Stor> {
+//!     index: u16,
+//!     name: S::String,
+//!     value: S::Bytes,
+//! }
+//!
+//! impl <S: Stor> Encode for Header<S> {
+//!     fn encode(&self, e: &mut Encoder) -> Result<(), CodecError> {
+//!         self.index.encode(e)?;
+//!         e.encode_str(self.name.as_ref())?;
+//!         e.encode_bytes(self.value.as_ref())
+//!     }
+//! }
+//!
+//! impl <'a, S: StorDecode<'a>> Decode<'a> for Header<S> {
+//!     fn decode(d: &mut Decoder<'a>) -> Result<Self, CodecError> {
+//!         Ok(Self {
+//!             index: d.decode()?,
+//!             name: S::decode_string(d)?,
+//!             value: S::decode_bytes(d)?,
+//!         })
+//!     }
+//! }
+//!
+//! let h = Header::<Ref>{ index: 1, name: "abc", value: &[0xaa, 0xbb] };
+//!
+//! let mut buff = [0u8; 32];
+//! let n = codec::encode(&h, &mut buff).unwrap();
+//! assert_eq!(n, codec::encoded_len(&h));
+//!
+//! // Decode borrowing from the buffer
+//! let (r, _) = codec::decode::<Header<Ref>>(&buff[..n]).unwrap();
+//! assert_eq!(r, h);
+//!
+//! // Or into fixed capacity storage
+//! let (v, _) = codec::decode::<Header<Heapless<4>>>(&buff[..n]).unwrap();
+//! assert_eq!(v.name, "abc");
+//! assert!(codec::decode::<Header<Heapless<2>>>(&buff[..n]).is_err());
+//! ```
+//!
+//! With the `derive` feature, `#[derive(Encode, Decode)]` generates these implementations.
+
+use core::fmt::{Debug, Display};
+
+use crate::{Bounded, BoundedVec, Const, Ref, StorError, TryFromStor};
+
+/// Derive [`Encode`] for types generic over [`Stor`](crate::Stor) (requires `derive` feature)
+#[cfg(feature = "derive")]
+pub use stor_derive::Encode;
+
+/// Derive [`Decode`] for types generic over [`Stor`](crate::Stor) (requires `derive` feature)
+#[cfg(feature = "derive")]
+pub use stor_derive::Decode;
+
+/// Error returned when encoding or decoding
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CodecError {
+    /// Buffer too short for encoding or decoding
+    BufferTooShort,
+    /// Invalid value in the encoded data
+    InvalidValue,
+    /// Decoding lists is not supported by the storage type
+    Unsupported,
+    /// Constructing the container failed
+    Stor(StorError),
+}
+
+impl Display for CodecError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CodecError::BufferTooShort => write!(f, "buffer too short"),
+            CodecError::InvalidValue => write!(f, "invalid value"),
+            CodecError::Unsupported => write!(f, "unsupported by storage type"),
+            CodecError::Stor(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<StorError> for CodecError {
+    fn from(e: StorError) -> Self {
+        CodecError::Stor(e)
+    }
+}
+
+/// Encode a value into the provided buffer, returning the encoded length
+pub fn encode<T: Encode + ?Sized>(value: &T, buff: &mut [u8]) -> Result<usize, CodecError> {
+    let mut e = Encoder::new(buff);
+    value.encode(&mut e)?;
+    Ok(e.len())
+}
+
+/// Compute the encoded length of a value
+pub fn encoded_len<T: Encode + ?Sized>(value: &T) -> usize {
+    let mut e = Encoder::sizer();
+    // Sizing encoders do not fail on length, and encoding is otherwise infallible
+    let _ = value.encode(&mut e);
+    e.len()
+}
+
+/// Decode a value from the provided buffer, returning the value and the decoded length
+pub fn decode<'a, T: Decode<'a>>(buff: &'a [u8]) -> Result<(T, usize), CodecError> {
+    let mut d = Decoder::new(buff);
+    let v = T::decode(&mut d)?;
+    Ok((v, d.position()))
+}
+
+/// [`Encode`] trait for types that can be written to an [`Encoder`]
+pub trait Encode {
+    /// Encode this value
+    fn encode(&self, e: &mut Encoder) -> Result<(), CodecError>;
+}
+
+/// [`Decode`] trait for types that can be read from a [`Decoder`], borrowing for `'a`
+pub trait Decode<'a>: Sized {
+    /// Decode a value
+    fn decode(d: &mut Decoder<'a>) -> Result<Self, CodecError>;
+}
+
+/// Encoder writes values to a buffer, or computes their length where created with [`Encoder::sizer`]
+pub struct Encoder<'b> {
+    buff: Option<&'b mut [u8]>,
+    index: usize,
+}
+
+impl <'b> Encoder<'b> {
+    /// Create a new encoder writing to the provided buffer
+    pub fn new(buff: &'b mut [u8]) -> Self {
+        Self { buff: Some(buff), index: 0 }
+    }
+
+    /// Create a new encoder computing encoded lengths without writing
+    pub fn sizer() -> Self {
+        Self { buff: None, index: 0 }
+    }
+
+    /// Fetch the number of bytes encoded
+    pub fn len(&self) -> usize {
+        self.index
+    }
+
+    /// Check whether no bytes have been encoded
+    pub fn is_empty(&self) -> bool {
+        self.index == 0
+    }
+
+    /// Write raw bytes
+    pub fn write(&mut self, data: &[u8]) -> Result<(), CodecError> {
+        if let Some(b) = &mut self.buff {
+            let d = b.get_mut(self.index..self.index + data.len()).ok_or(CodecError::BufferTooShort)?;
+            d.copy_from_slice(data);
+        }
+        self.index += data.len();
+        Ok(())
+    }
+
+    /// Encode a value
+    pub fn encode<T: Encode + ?Sized>(&mut self, value: &T) -> Result<(), CodecError> {
+        value.encode(self)
+    }
+
+    /// Encode a variable length unsigned integer, used for lengths and enum variants
+    pub fn encode_len(&mut self, mut value: usize) -> Result<(), CodecError> {
+        loop {
+            let b = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                return self.write(&[b]);
+            }
+            self.write(&[b | 0x80])?;
+        }
+    }
+
+    /// Encode a length prefixed string
+    pub fn encode_str(&mut self, value: &str) -> Result<(), CodecError> {
+        self.encode_bytes(value.as_bytes())
+    }
+
+    /// Encode length prefixed bytes
+    pub fn encode_bytes(&mut self, value: &[u8]) -> Result<(), CodecError> {
+        self.encode_len(value.len())?;
+        self.write(value)
+    }
+
+    /// Encode a length prefixed list
+    pub fn encode_list<T: Encode>(&mut self, value: &[T]) -> Result<(), CodecError> {
+        self.encode_len(value.len())?;
+        for v in value {
+            v.encode(self)?;
+        }
+        Ok(())
+    }
+}
+
+/// Decoder reads values from a buffer
+pub struct Decoder<'a> {
+    buff: &'a [u8],
+    index: usize,
+}
+
+impl <'a> Decoder<'a> {
+    /// Create a new decoder reading from the provided buffer
+    pub fn new(buff: &'a [u8]) -> Self {
+        Self { buff, index: 0 }
+    }
+
+    /// Fetch the number of bytes decoded
+    pub fn position(&self) -> usize {
+        self.index
+    }
+
+    /// Fetch the remaining undecoded bytes
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.buff[self.index..]
+    }
+
+    /// Read raw bytes
+    pub fn read(&mut self, len: usize) -> Result<&'a [u8], CodecError> {
+        let d = self.buff.get(self.index..).and_then(|b| b.get(..len)).ok_or(CodecError::BufferTooShort)?;
+        self.index += len;
+        Ok(d)
+    }
+
+    /// Decode a value
+    pub fn decode<T: Decode<'a>>(&mut self) -> Result<T, CodecError> {
+        T::decode(self)
+    }
+
+    /// Decode a variable length unsigned integer, used for lengths and enum variants
+    pub fn decode_len(&mut self) -> Result<usize, CodecError> {
+        let mut value = 0usize;
+        let mut shift = 0;
+        loop {
+            let b = self.read(1)?[0];
+            let v = ((b & 0x7f) as usize).checked_shl(shift).filter(|v| v >> shift == (b & 0x7f) as usize);
+            value |= v.ok_or(CodecError::InvalidValue)?;
+            if b & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+        }
+    }
+
+    /// Decode a length prefixed string, borrowing from the buffer
+    pub fn decode_str(&mut self) -> Result<&'a str, CodecError> {
+        let b = self.decode_bytes()?;
+        core::str::from_utf8(b).map_err(|_| CodecError::Stor(StorError::InvalidUtf8))
+    }
+
+    /// Decode length prefixed bytes, borrowing from the buffer
+    pub fn decode_bytes(&mut self) -> Result<&'a [u8], CodecError> {
+        let len = self.decode_len()?;
+        self.read(len)
+    }
+
+    /// Decode a length prefixed list, calling `f` with each item
+    ///
+    /// Lengths exceeding the remaining buffer are rejected before decoding items, bounding
+    /// the work done for untrusted input, so each item is expected to encode to at least one byte.
+    pub fn decode_list<T: Decode<'a>, F: FnMut(T) -> Result<(), CodecError>>(&mut self, mut f: F) -> Result<usize, CodecError> {
+        let len = self.decode_len()?;
+        if len > self.remaining().len() {
+            return Err(CodecError::BufferTooShort);
+        }
+
+        for _ in 0..len {
+            f(T::decode(self)?)?;
+        }
+        Ok(len)
+    }
+}
+
+/// [`StorDecode`] trait constructs [`Stor`](crate::Stor) containers from a [`Decoder`]
+///
+/// Strings and bytes are constructed using [`TryFromStor`], borrowing from the
+/// buffer where supported, with lists decoded item by item.
+pub trait StorDecode<'a>: TryFromStor<'a> {
+    /// Decode a list of items
+    fn decode_list<T: Debug + Decode<'a>>(d: &mut Decoder<'a>) -> Result<Self::List<T>, CodecError>;
+
+    /// Decode a string
+    fn decode_string(d: &mut Decoder<'a>) -> Result<Self::String, CodecError> {
+        let s = d.decode_str()?;
+        Ok(Self::try_string_from(s)?)
+    }
+
+    /// Decode bytes
+    fn decode_bytes(d: &mut Decoder<'a>) -> Result<Self::Bytes, CodecError> {
+        let b = d.decode_bytes()?;
+        Ok(Self::try_bytes_from(b)?)
+    }
+}
+
+/// [`Ref`] lists cannot borrow arbitrary items from the buffer, so return [`CodecError::Unsupported`]
+impl <'a> StorDecode<'a> for Ref<'a> {
+    fn decode_list<T: Debug + Decode<'a>>(_d: &mut Decoder<'a>) -> Result<Self::List<T>, CodecError> {
+        Err(CodecError::Unsupported)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl <'a> StorDecode<'a> for crate::Owned {
+    fn decode_list<T: Debug + Decode<'a>>(d: &mut Decoder<'a>) -> Result<Self::List<T>, CodecError> {
+        let mut l = alloc::vec::Vec::new();
+        d.decode_list(|v| {
+            l.push(v);
+            Ok(())
+        })?;
+        Ok(l)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl <'a> StorDecode<'a> for crate::Boxed {
+    fn decode_list<T: Debug + Decode<'a>>(d: &mut Decoder<'a>) -> Result<Self::List<T>, CodecError> {
+        crate::Owned::decode_list(d).map(|l| l.into_boxed_slice())
+    }
+}
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl <'a> StorDecode<'a> for crate::Shared {
+    fn decode_list<T: Debug + Decode<'a>>(d: &mut Decoder<'a>) -> Result<Self::List<T>, CodecError> {
+        crate::Owned::decode_list(d).map(|l| l.into())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl <'a> StorDecode<'a> for crate::Local {
+    fn decode_list<T: Debug + Decode<'a>>(d: &mut Decoder<'a>) -> Result<Self::List<T>, CodecError> {
+        crate::Owned::decode_list(d).map(|l| l.into())
+    }
+}
+
+/// [`Cow`](crate::Cow) borrows strings and bytes from the buffer, and decodes lists as owned
+#[cfg(feature = "alloc")]
+impl <'a> StorDecode<'a> for crate::Cow<'a> {
+    fn decode_list<T: Debug + Decode<'a>>(d: &mut Decoder<'a>) -> Result<Self::List<T>, CodecError> {
+        crate::Owned::decode_list(d).map(crate::CowList::Owned)
+    }
+}
+
+impl <'a, const N: usize> StorDecode<'a> for Const<N> {
+    fn decode_list<T: Debug + Decode<'a>>(d: &mut Decoder<'a>) -> Result<Self::List<T>, CodecError> {
+        let len = d.decode_len()?;
+        if len != N {
+            return Err(StorError::LengthMismatch { expected: N, actual: len }.into());
+        }
+
+        let mut l = BoundedVec::<T, N>::new();
+        for _ in 0..N {
+            // Capacity is `N` so this cannot fail
+            let _ = l.push(d.decode()?);
+        }
+        l.into_array().map_err(|_| StorError::LengthMismatch { expected: N, actual: len }.into())
+    }
+}
+
+impl <'a, const N: usize> StorDecode<'a> for Bounded<N> {
+    fn decode_list<T: Debug + Decode<'a>>(d: &mut Decoder<'a>) -> Result<Self::List<T>, CodecError> {
+        let mut l = BoundedVec::new();
+        d.decode_list(|v| l.push(v).map_err(|_| StorError::CapacityOverflow.into()))?;
+        Ok(l)
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl <'a, const L: usize, const S: usize, const B: usize> StorDecode<'a> for crate::HeaplessSized<L, S, B> {
+    fn decode_list<T: Debug + Decode<'a>>(d: &mut Decoder<'a>) -> Result<Self::List<T>, CodecError> {
+        let mut l = heapless::Vec::new();
+        d.decode_list(|v| l.push(v).map_err(|_| StorError::CapacityOverflow.into()))?;
+        Ok(l)
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl <'a, const L: usize, const S: usize, const B: usize> StorDecode<'a> for crate::ArrayVecSized<L, S, B> {
+    fn decode_list<T: Debug + Decode<'a>>(d: &mut Decoder<'a>) -> Result<Self::List<T>, CodecError> {
+        let mut l = arrayvec::ArrayVec::new();
+        d.decode_list(|v| l.try_push(v).map_err(|_| StorError::CapacityOverflow.into()))?;
+        Ok(l)
+    }
+}
+
+#[cfg(feature = "tinyvec")]
+impl <'a, const L: usize, const S: usize, const B: usize> StorDecode<'a> for crate::TinyVecSized<L, S, B> {
+    fn decode_list<T: Debug + Decode<'a>>(d: &mut Decoder<'a>) -> Result<Self::List<T>, CodecError> {
+        Bounded::<L>::decode_list(d)
+    }
+}
+
+macro_rules! impl_codec_int {
+    ($($t:ty),*) => {
+        $(
+            impl Encode for $t {
+                fn encode(&self, e: &mut Encoder) -> Result<(), CodecError> {
+                    e.write(&self.to_le_bytes())
+                }
+            }
+
+            impl <'a> Decode<'a> for $t {
+                fn decode(d: &mut Decoder<'a>) -> Result<Self, CodecError> {
+                    let b = d.read(core::mem::size_of::<$t>())?;
+                    // Length is checked by `read`
+                    Ok(<$t>::from_le_bytes(b.try_into().unwrap()))
+                }
+            }
+        )*
+    };
+}
+
+impl_codec_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl Encode for bool {
+    fn encode(&self, e: &mut Encoder) -> Result<(), CodecError> {
+        e.write(&[*self as u8])
+    }
+}
+
+impl <'a> Decode<'a> for bool {
+    fn decode(d: &mut Decoder<'a>) -> Result<Self, CodecError> {
+        match d.read(1)?[0] {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(CodecError::InvalidValue),
+        }
+    }
+}
+
+impl <T: Encode> Encode for Option<T> {
+    fn encode(&self, e: &mut Encoder) -> Result<(), CodecError> {
+        match self {
+            None => false.encode(e),
+            Some(v) => {
+                true.encode(e)?;
+                v.encode(e)
+            }
+        }
+    }
+}
+
+impl <'a, T: Decode<'a>> Decode<'a> for Option<T> {
+    fn decode(d: &mut Decoder<'a>) -> Result<Self, CodecError> {
+        match bool::decode(d)? {
+            false => Ok(None),
+            true => T::decode(d).map(Some),
+        }
+    }
+}
+
+impl <T: Encode + ?Sized> Encode for &T {
+    fn encode(&self, e: &mut Encoder) -> Result<(), CodecError> {
+        (**self).encode(e)
+    }
+}
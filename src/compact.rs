@@ -0,0 +1,45 @@
+//! [`Compact`] marker for owned storage with [`CompactString`] strings (requires `compact_str` feature)
+
+use core::fmt::Debug;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use compact_str::CompactString;
+
+use crate::owned_string::impl_owned_string;
+use crate::Stor;
+
+/// Compact marker uses [`Vec`] for lists and bytes, with [`CompactString`] strings that hold
+/// up to 24 bytes inline in the same space as a [`String`]
+///
+/// Conversion to and from [`Owned`](crate::Owned) keeps heap allocated strings without copying.
+///
+/// ```
+/// use stor::{Stor, Compact, TryFromStor};
+///
+/// #[derive(Debug)]
+/// struct Entry<S: Stor> {
+///     name: S::String,
+///     value: S::Bytes,
+/// }
+///
+/// let e = Entry::<Compact> {
+///     name: Compact::try_string_from("abc").unwrap(),
+///     value: vec![0xaa, 0xbb],
+/// };
+/// assert_eq!(e.name, "abc");
+/// assert!(!e.name.is_heap_allocated());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Compact;
+
+impl Stor for Compact {
+    type List<T: Debug> = Vec<T>;
+    type String = CompactString;
+    type Bytes = Vec<u8>;
+}
+
+impl_owned_string!(Compact, CompactString, |string, value| string.push_str(value));
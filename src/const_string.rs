@@ -0,0 +1,221 @@
+//! [`ConstString`] fixed capacity string type used by the [`Const`](crate::Const) marker
+
+use core::borrow::Borrow;
+use core::fmt::{Debug, Display, Write};
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+use crate::CapacityError;
+
+/// Fixed capacity UTF-8 string, stored inline in `[u8; N]` with a validated length
+///
+/// ```
+/// use stor::ConstString;
+///
+/// // Construct statics at compile time
+/// static NAME: ConstString<8> = ConstString::from_str_const("stor");
+/// assert_eq!(NAME, "stor");
+///
+/// // Or at runtime, failing where the capacity is exceeded
+/// let mut s = ConstString::<8>::try_from_str("abc").unwrap();
+/// s.push_str("def").unwrap();
+/// assert_eq!(s.as_str(), "abcdef");
+/// assert!(s.push_str("ghi").is_err());
+/// ```
+#[derive(Copy, Clone)]
+pub struct ConstString<const N: usize> {
+    buff: [u8; N],
+    len: usize,
+}
+
+impl <const N: usize> ConstString<N> {
+    /// Create a new empty [`ConstString`]
+    pub const fn new() -> Self {
+        Self { buff: [0u8; N], len: 0 }
+    }
+
+    /// Create a [`ConstString`] from a string slice, returning [`CapacityError`] if this does not fit
+    pub const fn try_from_str(s: &str) -> Result<Self, CapacityError> {
+        let b = s.as_bytes();
+        if b.len() > N {
+            return Err(CapacityError);
+        }
+
+        let mut buff = [0u8; N];
+        let mut i = 0;
+        while i < b.len() {
+            buff[i] = b[i];
+            i += 1;
+        }
+
+        Ok(Self { buff, len: b.len() })
+    }
+
+    /// Create a [`ConstString`] from a string slice, for use in `const` and `static` items
+    ///
+    /// # Panics
+    /// If the string does not fit, which is a compile time error in const contexts
+    pub const fn from_str_const(s: &str) -> Self {
+        match Self::try_from_str(s) {
+            Ok(v) => v,
+            Err(_) => panic!("string exceeds ConstString capacity"),
+        }
+    }
+
+    /// Fetch the string contents
+    pub const fn as_str(&self) -> &str {
+        let (b, _) = self.buff.split_at(self.len);
+        // SAFETY: contents are only written from valid UTF-8 and always on char boundaries
+        unsafe { core::str::from_utf8_unchecked(b) }
+    }
+
+    /// Fetch the string contents as bytes
+    pub const fn as_bytes(&self) -> &[u8] {
+        self.as_str().as_bytes()
+    }
+
+    /// Fetch the string length in bytes
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check whether the string is empty
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Fetch the string capacity in bytes
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Append a string slice, returning [`CapacityError`] if this does not fit
+    pub fn push_str(&mut self, s: &str) -> Result<(), CapacityError> {
+        let b = s.as_bytes();
+        if self.len + b.len() > N {
+            return Err(CapacityError);
+        }
+
+        self.buff[self.len..][..b.len()].copy_from_slice(b);
+        self.len += b.len();
+
+        Ok(())
+    }
+
+    /// Append a character, returning [`CapacityError`] if this does not fit
+    pub fn push(&mut self, c: char) -> Result<(), CapacityError> {
+        self.push_str(c.encode_utf8(&mut [0u8; 4]))
+    }
+
+    /// Shorten the string to the provided length in bytes
+    ///
+    /// # Panics
+    /// If `len` does not lie on a char boundary
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            assert!(self.as_str().is_char_boundary(len), "truncate must lie on a char boundary");
+            self.len = len;
+        }
+    }
+
+    /// Remove all contents from the string
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl <const N: usize> Default for ConstString<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <const N: usize> Deref for ConstString<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl <const N: usize> AsRef<str> for ConstString<N> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl <const N: usize> AsRef<[u8]> for ConstString<N> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl <const N: usize> Borrow<str> for ConstString<N> {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl <'a, const N: usize> TryFrom<&'a str> for ConstString<N> {
+    type Error = CapacityError;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        Self::try_from_str(s)
+    }
+}
+
+impl <const N: usize> Debug for ConstString<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl <const N: usize> Display for ConstString<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+impl <const N: usize> Write for ConstString<N> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.push_str(s).map_err(|_| core::fmt::Error)
+    }
+}
+
+impl <const N: usize, const M: usize> PartialEq<ConstString<M>> for ConstString<N> {
+    fn eq(&self, other: &ConstString<M>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl <const N: usize> Eq for ConstString<N> {}
+
+impl <const N: usize> PartialEq<str> for ConstString<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl <'a, const N: usize> PartialEq<&'a str> for ConstString<N> {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl <const N: usize> PartialOrd for ConstString<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl <const N: usize> Ord for ConstString<N> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl <const N: usize> Hash for ConstString<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
@@ -0,0 +1,220 @@
+//! [`Cow`] marker for copy-on-write storage
+
+use core::borrow::Borrow;
+use core::fmt::Debug;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+use alloc::borrow::Cow as StdCow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{FromOwned, IntoOwned, RefList, Stor};
+
+/// Cow marker uses copy-on-write containers, borrowing until modified
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Cow<'a> (PhantomData<&'a ()>);
+
+impl <'a> Stor for Cow<'a> {
+    type List<T: Debug> = CowList<'a, T>;
+    type String = StdCow<'a, str>;
+    type Bytes = StdCow<'a, [u8]>;
+}
+
+impl <'a> IntoOwned for Cow<'a> {
+    fn into_owned_list<T: Debug + Clone>(list: Self::List<T>) -> Vec<T> {
+        list.into_owned()
+    }
+
+    fn into_owned_string(string: Self::String) -> String {
+        string.into_owned()
+    }
+
+    fn into_owned_bytes(bytes: Self::Bytes) -> Vec<u8> {
+        bytes.into_owned()
+    }
+}
+
+impl <'a> FromOwned for Cow<'a> {
+    fn from_owned_list<T: Debug>(list: Vec<T>) -> Self::List<T> {
+        CowList::Owned(list)
+    }
+
+    fn from_owned_string(string: String) -> Self::String {
+        StdCow::Owned(string)
+    }
+
+    fn from_owned_bytes(bytes: Vec<u8>) -> Self::Bytes {
+        StdCow::Owned(bytes)
+    }
+}
+
+/// Copy-on-write list, equivalent to `Cow<'a, [T]>`
+///
+/// As with [`RefList`] this stands in for [`alloc::borrow::Cow`] as the generic
+/// `List<T>` type cannot require `T: 'a` or `T: Clone`.
+pub enum CowList<'a, T> {
+    /// Borrowed list
+    Borrowed(RefList<'a, T>),
+    /// Owned list
+    Owned(Vec<T>),
+}
+
+impl <'a, T> CowList<'a, T> {
+    /// Fetch the list contents as a slice
+    pub fn as_slice(&self) -> &[T] {
+        match self {
+            CowList::Borrowed(b) => b.as_slice(),
+            CowList::Owned(o) => o.as_slice(),
+        }
+    }
+
+    /// Check whether the list is borrowed
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self, CowList::Borrowed(_))
+    }
+
+    /// Check whether the list is owned
+    pub fn is_owned(&self) -> bool {
+        matches!(self, CowList::Owned(_))
+    }
+
+    /// Fetch a mutable reference to the owned list, cloning borrowed contents
+    pub fn to_mut(&mut self) -> &mut Vec<T> where T: Clone {
+        if let CowList::Borrowed(b) = self {
+            *self = CowList::Owned(b.as_slice().to_vec());
+        }
+
+        match self {
+            CowList::Owned(o) => o,
+            CowList::Borrowed(_) => unreachable!(),
+        }
+    }
+
+    /// Convert into an owned list, cloning borrowed contents
+    pub fn into_owned(self) -> Vec<T> where T: Clone {
+        match self {
+            CowList::Borrowed(b) => b.as_slice().to_vec(),
+            CowList::Owned(o) => o,
+        }
+    }
+}
+
+impl <'a, T: Clone> Clone for CowList<'a, T> {
+    fn clone(&self) -> Self {
+        match self {
+            CowList::Borrowed(b) => CowList::Borrowed(*b),
+            CowList::Owned(o) => CowList::Owned(o.clone()),
+        }
+    }
+}
+
+impl <'a, T> Default for CowList<'a, T> {
+    fn default() -> Self {
+        CowList::Borrowed(RefList::default())
+    }
+}
+
+impl <'a, T: Debug> Debug for CowList<'a, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl <'a, T> Deref for CowList<'a, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl <'a, T> AsRef<[T]> for CowList<'a, T> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl <'a, T> Borrow<[T]> for CowList<'a, T> {
+    fn borrow(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl <'a, T> From<RefList<'a, T>> for CowList<'a, T> {
+    fn from(list: RefList<'a, T>) -> Self {
+        CowList::Borrowed(list)
+    }
+}
+
+impl <'a, T> From<&'a [T]> for CowList<'a, T> {
+    fn from(slice: &'a [T]) -> Self {
+        CowList::Borrowed(RefList::new(slice))
+    }
+}
+
+impl <'a, T> From<Vec<T>> for CowList<'a, T> {
+    fn from(list: Vec<T>) -> Self {
+        CowList::Owned(list)
+    }
+}
+
+impl <'a, T: Clone> From<StdCow<'a, [T]>> for CowList<'a, T> {
+    fn from(c: StdCow<'a, [T]>) -> Self {
+        match c {
+            StdCow::Borrowed(b) => CowList::Borrowed(RefList::new(b)),
+            StdCow::Owned(o) => CowList::Owned(o),
+        }
+    }
+}
+
+impl <'a, T: 'a + Clone> From<CowList<'a, T>> for StdCow<'a, [T]> {
+    fn from(c: CowList<'a, T>) -> Self {
+        match c {
+            CowList::Borrowed(b) => StdCow::Borrowed(b.into_slice()),
+            CowList::Owned(o) => StdCow::Owned(o),
+        }
+    }
+}
+
+impl <'a, 'b, T: PartialEq> PartialEq<CowList<'b, T>> for CowList<'a, T> {
+    fn eq(&self, other: &CowList<'b, T>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl <'a, T: Eq> Eq for CowList<'a, T> {}
+
+impl <'a, T: PartialEq> PartialEq<[T]> for CowList<'a, T> {
+    fn eq(&self, other: &[T]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl <'a, T: PartialEq, const N: usize> PartialEq<[T; N]> for CowList<'a, T> {
+    fn eq(&self, other: &[T; N]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl <'a, T: PartialOrd> PartialOrd for CowList<'a, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl <'a, T: Ord> Ord for CowList<'a, T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl <'a, T: Hash> Hash for CowList<'a, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
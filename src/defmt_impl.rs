@@ -0,0 +1,115 @@
+//! [`defmt`] support for markers and containers provided by this crate (requires `defmt` feature)
+//!
+//! `#[derive(defmt::Format)]` adds a `Format` bound for each field type, so deriving
+//! on a type generic over `S: Stor` works for any marker without additional bounds.
+//! Generic functions formatting strings or bytes may use [`StorFormat`] in place of
+//! bounding each associated type.
+
+use defmt::{Format, Formatter};
+
+use crate::{BoundedVec, ConstString, RefList, Stor};
+
+/// [`StorFormat`] bounds [`Stor`] string and byte containers by [`Format`]
+///
+/// This is implemented for all markers where the `defmt` feature is enabled.
+/// Lists implement [`Format`] where their items do, though as this cannot be
+/// expressed for all `T` these bounds are added per field by derived impls.
+///
+/// ```
+/// use stor::{Stor, StorFormat};
+///
+/// #[derive(Debug, defmt::Format)]
+/// enum Something<S: Stor> {
+///     List(S::List<u32>),
+///     Headers(S::List<Header<S>>),
+///     Str(S::String),
+///     Bytes(S::Bytes),
+/// }
+///
+/// #[derive(Debug, defmt::Format)]
+/// struct Header<S: Stor> {
+///     name: S::String,
+///     value: S::Bytes,
+/// }
+///
+/// fn log_header<S: StorFormat>(h: &Header<S>) {
+///     defmt::info!("header {}: {}", h.name, h.value);
+/// }
+/// ```
+pub trait StorFormat: Stor<String: Format, Bytes: Format> + Format {}
+
+impl <S: Stor<String: Format, Bytes: Format> + Format> StorFormat for S {}
+
+impl <'a, T: Format> Format for RefList<'a, T> {
+    fn format(&self, f: Formatter) {
+        self.as_slice().format(f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl <'a, T: Format> Format for crate::CowList<'a, T> {
+    fn format(&self, f: Formatter) {
+        self.as_slice().format(f)
+    }
+}
+
+#[cfg(feature = "bumpalo")]
+impl <'a, T: Format> Format for crate::BumpList<'a, T> {
+    fn format(&self, f: Formatter) {
+        self.as_slice().format(f)
+    }
+}
+
+#[cfg(feature = "slab")]
+impl <'a, T: Format> Format for crate::SlabList<'a, T> {
+    fn format(&self, f: Formatter) {
+        self.as_slice().format(f)
+    }
+}
+
+#[cfg(feature = "slab")]
+impl <'a> Format for crate::SlabString<'a> {
+    fn format(&self, f: Formatter) {
+        self.as_str().format(f)
+    }
+}
+
+#[cfg(all(feature = "pool", target_has_atomic = "8"))]
+impl <'a, T: Format, const B: usize> Format for crate::PoolList<'a, T, B> {
+    fn format(&self, f: Formatter) {
+        self.as_slice().format(f)
+    }
+}
+
+#[cfg(all(feature = "pool", target_has_atomic = "8"))]
+impl <'a, const B: usize> Format for crate::PoolString<'a, B> {
+    fn format(&self, f: Formatter) {
+        self.as_str().format(f)
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl <const N: usize> Format for crate::InlineString<N> {
+    fn format(&self, f: Formatter) {
+        self.as_str().format(f)
+    }
+}
+
+#[cfg(all(feature = "bytes", target_has_atomic = "ptr"))]
+impl Format for crate::BufString {
+    fn format(&self, f: Formatter) {
+        self.as_str().format(f)
+    }
+}
+
+impl <const N: usize> Format for ConstString<N> {
+    fn format(&self, f: Formatter) {
+        self.as_str().format(f)
+    }
+}
+
+impl <T: Format, const N: usize> Format for BoundedVec<T, N> {
+    fn format(&self, f: Formatter) {
+        self.as_slice().format(f)
+    }
+}
@@ -0,0 +1,120 @@
+//! Support functions for code generated by [`StorConvert`](crate::StorConvert), not public API
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+use crate::RefList;
+
+#[cfg(feature = "slab")]
+use crate::{CapacityError, SlabBuffer, SlabList};
+
+/// Expand the provided items only where the `alloc` feature is enabled
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "alloc")]
+macro_rules! __if_alloc {
+    ($($t:tt)*) => { $($t)* };
+}
+
+/// Expand the provided items only where the `alloc` feature is enabled
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "alloc"))]
+macro_rules! __if_alloc {
+    ($($t:tt)*) => {};
+}
+
+/// Expand the provided items only where the `bumpalo` feature is enabled
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "bumpalo")]
+macro_rules! __if_bumpalo {
+    ($($t:tt)*) => { $($t)* };
+}
+
+/// Expand the provided items only where the `bumpalo` feature is enabled
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "bumpalo"))]
+macro_rules! __if_bumpalo {
+    ($($t:tt)*) => {};
+}
+
+/// Expand the provided items only where the `bytes` feature is enabled
+#[doc(hidden)]
+#[macro_export]
+#[cfg(all(feature = "bytes", target_has_atomic = "ptr"))]
+macro_rules! __if_bytes {
+    ($($t:tt)*) => { $($t)* };
+}
+
+/// Expand the provided items only where the `bytes` feature is enabled
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(all(feature = "bytes", target_has_atomic = "ptr")))]
+macro_rules! __if_bytes {
+    ($($t:tt)*) => {};
+}
+
+/// Expand the provided items only where the `slab` feature is enabled
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "slab")]
+macro_rules! __if_slab {
+    ($($t:tt)*) => { $($t)* };
+}
+
+/// Expand the provided items only where the `slab` feature is enabled
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "slab"))]
+macro_rules! __if_slab {
+    ($($t:tt)*) => {};
+}
+
+#[cfg(feature = "alloc")]
+pub fn to_owned_string<S: AsRef<str> + ?Sized>(s: &S) -> String {
+    String::from(s.as_ref())
+}
+
+#[cfg(feature = "alloc")]
+pub fn to_owned_bytes<B: AsRef<[u8]> + ?Sized>(b: &B) -> Vec<u8> {
+    b.as_ref().to_vec()
+}
+
+#[cfg(feature = "alloc")]
+pub fn to_owned_list<T, U, L: AsRef<[T]> + ?Sized, F: FnMut(&T) -> U>(l: &L, f: F) -> Vec<U> {
+    l.as_ref().iter().map(f).collect()
+}
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+pub fn to_shared_list<T, U, L: AsRef<[T]> + ?Sized, F: FnMut(&T) -> U>(l: &L, f: F) -> alloc::sync::Arc<[U]> {
+    l.as_ref().iter().map(f).collect()
+}
+
+pub fn as_ref_string<S: AsRef<str> + ?Sized>(s: &S) -> &str {
+    s.as_ref()
+}
+
+pub fn as_ref_bytes<B: AsRef<[u8]> + ?Sized>(b: &B) -> &[u8] {
+    b.as_ref()
+}
+
+pub fn as_ref_list<'a, T: 'a, L: AsRef<[T]> + ?Sized>(l: &'a L) -> RefList<'a, T> {
+    RefList::new(l.as_ref())
+}
+
+#[cfg(feature = "slab")]
+pub fn to_slab_list<'a, T, U, L, F>(slab: &'a SlabBuffer<'_>, l: &L, mut f: F) -> Result<SlabList<'a, U>, CapacityError>
+where
+    L: AsRef<[T]> + ?Sized,
+    F: FnMut(&T) -> Result<U, CapacityError>,
+{
+    let l = l.as_ref();
+    let mut s = slab.list(l.len())?;
+    for v in l {
+        // Capacity is reserved for every item so this cannot fail
+        let _ = s.push(f(v)?);
+    }
+    Ok(s)
+}
@@ -0,0 +1,53 @@
+//! Error types for [`Stor`](crate::Stor) container operations
+
+use core::fmt::Display;
+
+/// Error returned when a container does not have the capacity for an operation
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CapacityError;
+
+impl Display for CapacityError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "insufficient capacity")
+    }
+}
+
+/// Error returned when constructing [`Stor`](crate::Stor) containers
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StorError {
+    /// Container capacity exceeded
+    CapacityOverflow,
+    /// Length does not match fixed size container
+    LengthMismatch {
+        /// Length required by the container
+        expected: usize,
+        /// Length provided
+        actual: usize,
+    },
+    /// String data is not valid UTF-8
+    InvalidUtf8,
+}
+
+impl Display for StorError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            StorError::CapacityOverflow => write!(f, "insufficient capacity"),
+            StorError::LengthMismatch { expected, actual } => {
+                write!(f, "length mismatch (expected {expected}, actual {actual})")
+            }
+            StorError::InvalidUtf8 => write!(f, "invalid utf-8"),
+        }
+    }
+}
+
+impl From<CapacityError> for StorError {
+    fn from(_: CapacityError) -> Self {
+        StorError::CapacityOverflow
+    }
+}
+
+impl From<core::str::Utf8Error> for StorError {
+    fn from(_: core::str::Utf8Error) -> Self {
+        StorError::InvalidUtf8
+    }
+}
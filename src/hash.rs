@@ -0,0 +1,142 @@
+//! Content hashing consistent across [`Stor`](crate::Stor) markers
+//!
+//! These functions hash containers by content, so equal strings, bytes or lists
+//! produce the same hash whichever marker holds them. Container [`Hash`] impls
+//! for all markers are consistent with these, hashing as `str` or `[T]` slices.
+//!
+//! With the `hash32` feature, `hash32_` variants support `hash32` hashers as used
+//! by `heapless` maps, and [`ConstString`](crate::ConstString), [`BoundedVec`](crate::BoundedVec)
+//! and [`RefList`](crate::RefList) implement `hash32::Hash` consistently with `heapless` containers.
+//!
+//! ```
+//! use std::collections::hash_map::DefaultHasher;
+//! use std::hash::Hasher;
+//! use stor::{Stor, Owned, Ref, Const, RefList, hash};
+//!
+//! fn content<L: AsRef<[u8]>>(l: &L) -> u64 {
+//!     let mut h = DefaultHasher::new();
+//!     hash::hash_list(l, &mut h);
+//!     h.finish()
+//! }
+//!
+//! let o: <Owned as Stor>::List<u8> = vec![1, 2, 3];
+//! let r: <Ref as Stor>::List<u8> = RefList::new(&[1, 2, 3]);
+//! let c: <Const<3> as Stor>::List<u8> = [1, 2, 3];
+//!
+//! assert_eq!(content(&o), content(&r));
+//! assert_eq!(content(&o), content(&c));
+//! ```
+
+use core::hash::{Hash, Hasher};
+
+/// Hash the contents of a string container
+pub fn hash_string<S: AsRef<str> + ?Sized, H: Hasher>(s: &S, state: &mut H) {
+    s.as_ref().hash(state)
+}
+
+/// Hash the contents of a byte container
+pub fn hash_bytes<B: AsRef<[u8]> + ?Sized, H: Hasher>(b: &B, state: &mut H) {
+    b.as_ref().hash(state)
+}
+
+/// Hash the contents of a list container, including the list length
+pub fn hash_list<T: Hash, L: AsRef<[T]> + ?Sized, H: Hasher>(l: &L, state: &mut H) {
+    l.as_ref().hash(state)
+}
+
+/// Hash the contents of a string container with a [`hash32`] hasher
+#[cfg(feature = "hash32")]
+pub fn hash32_string<S: AsRef<str> + ?Sized, H: hash32::Hasher>(s: &S, state: &mut H) {
+    hash32::Hash::hash(s.as_ref(), state)
+}
+
+/// Hash the contents of a byte container with a [`hash32`] hasher
+#[cfg(feature = "hash32")]
+pub fn hash32_bytes<B: AsRef<[u8]> + ?Sized, H: hash32::Hasher>(b: &B, state: &mut H) {
+    hash32::Hash::hash(b.as_ref(), state)
+}
+
+/// Hash the contents of a list container with a [`hash32`] hasher, including the list length
+///
+/// Unlike [`hash32::Hash`] for arrays, this supports lists of any length.
+#[cfg(feature = "hash32")]
+pub fn hash32_list<T: hash32::Hash, L: AsRef<[T]> + ?Sized, H: hash32::Hasher>(l: &L, state: &mut H) {
+    hash32::Hash::hash(l.as_ref(), state)
+}
+
+#[cfg(feature = "hash32")]
+impl <const N: usize> hash32::Hash for crate::ConstString<N> {
+    fn hash<H: hash32::Hasher>(&self, state: &mut H) {
+        hash32_string(self, state)
+    }
+}
+
+#[cfg(feature = "hash32")]
+impl <T: hash32::Hash, const N: usize> hash32::Hash for crate::BoundedVec<T, N> {
+    fn hash<H: hash32::Hasher>(&self, state: &mut H) {
+        hash32_list(self, state)
+    }
+}
+
+#[cfg(feature = "hash32")]
+impl <'a, T: hash32::Hash> hash32::Hash for crate::RefList<'a, T> {
+    fn hash<H: hash32::Hasher>(&self, state: &mut H) {
+        hash32_list(self, state)
+    }
+}
+
+#[cfg(all(feature = "hash32", feature = "alloc"))]
+impl <'a, T: hash32::Hash> hash32::Hash for crate::CowList<'a, T> {
+    fn hash<H: hash32::Hasher>(&self, state: &mut H) {
+        hash32_list(self, state)
+    }
+}
+
+#[cfg(all(feature = "hash32", feature = "slab"))]
+impl <'a, T: hash32::Hash> hash32::Hash for crate::SlabList<'a, T> {
+    fn hash<H: hash32::Hasher>(&self, state: &mut H) {
+        hash32_list(self, state)
+    }
+}
+
+#[cfg(all(feature = "hash32", feature = "slab"))]
+impl <'a> hash32::Hash for crate::SlabString<'a> {
+    fn hash<H: hash32::Hasher>(&self, state: &mut H) {
+        hash32_string(self, state)
+    }
+}
+
+#[cfg(all(feature = "hash32", feature = "pool", target_has_atomic = "8"))]
+impl <'a, T: hash32::Hash, const B: usize> hash32::Hash for crate::PoolList<'a, T, B> {
+    fn hash<H: hash32::Hasher>(&self, state: &mut H) {
+        hash32_list(self, state)
+    }
+}
+
+#[cfg(all(feature = "hash32", feature = "pool", target_has_atomic = "8"))]
+impl <'a, const B: usize> hash32::Hash for crate::PoolString<'a, B> {
+    fn hash<H: hash32::Hasher>(&self, state: &mut H) {
+        hash32_string(self, state)
+    }
+}
+
+#[cfg(all(feature = "hash32", feature = "smallvec"))]
+impl <const N: usize> hash32::Hash for crate::InlineString<N> {
+    fn hash<H: hash32::Hasher>(&self, state: &mut H) {
+        hash32_string(self, state)
+    }
+}
+
+#[cfg(all(feature = "hash32", feature = "bytes", target_has_atomic = "ptr"))]
+impl hash32::Hash for crate::BufString {
+    fn hash<H: hash32::Hasher>(&self, state: &mut H) {
+        hash32_string(self, state)
+    }
+}
+
+#[cfg(all(feature = "hash32", feature = "bumpalo"))]
+impl <'a, T: hash32::Hash> hash32::Hash for crate::BumpList<'a, T> {
+    fn hash<H: hash32::Hasher>(&self, state: &mut H) {
+        hash32_list(self, state)
+    }
+}
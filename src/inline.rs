@@ -0,0 +1,312 @@
+//! [`Inline`] marker for storage holding short contents inline and spilling to the heap (requires `smallvec` feature)
+
+use core::borrow::Borrow;
+use core::fmt::{Debug, Display, Write};
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use smallvec::SmallVec;
+
+use crate::codec::{CodecError, Decode, Decoder, StorDecode};
+use crate::{CapacityError, FromOwned, IntoOwned, Stor, StorError, StorMut, TryFromStor};
+
+/// Inline marker uses [`SmallVec`] and [`InlineString`] containers, holding up to `N` items
+/// (or bytes) inline and spilling to the heap beyond this
+///
+/// This suits types where most contents are short but some are long, as unlike
+/// [`Heapless`](crate::Heapless) long contents are accepted, and unlike [`Owned`](crate::Owned)
+/// short contents do not allocate. Conversion from [`Ref`](crate::Ref) uses [`TryFromStor`],
+/// which never fails, and from [`Owned`](crate::Owned) uses [`FromOwned`], which keeps
+/// existing allocations.
+///
+/// ```
+/// use stor::{Stor, Inline, TryFromStor};
+///
+/// #[derive(Debug)]
+/// struct Header<S: Stor> {
+///     name: S::String,
+///     value: S::Bytes,
+/// }
+///
+/// let h = Header::<Inline<8>> {
+///     name: Inline::try_string_from("abc").unwrap(),
+///     value: Inline::try_bytes_from(&[0xaa; 16]).unwrap(),
+/// };
+/// assert!(!h.name.spilled());
+/// assert!(h.value.spilled());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Inline<const N: usize>;
+
+impl <const N: usize> Stor for Inline<N> {
+    type List<T: Debug> = SmallVec<[T; N]>;
+    type String = InlineString<N>;
+    type Bytes = SmallVec<[u8; N]>;
+}
+
+/// UTF-8 string holding up to `N` bytes inline, used by the [`Inline`] marker
+#[derive(Clone, Default)]
+pub struct InlineString<const N: usize> {
+    buff: SmallVec<[u8; N]>,
+}
+
+impl <const N: usize> InlineString<N> {
+    /// Create a new empty [`InlineString`]
+    pub const fn new() -> Self {
+        Self { buff: SmallVec::new_const() }
+    }
+
+    /// Create an [`InlineString`] from UTF-8 bytes, returning the bytes if these are not valid
+    pub fn from_utf8(buff: SmallVec<[u8; N]>) -> Result<Self, SmallVec<[u8; N]>> {
+        match core::str::from_utf8(&buff) {
+            Ok(_) => Ok(Self { buff }),
+            Err(_) => Err(buff),
+        }
+    }
+
+    /// Fetch the string contents
+    pub fn as_str(&self) -> &str {
+        // SAFETY: contents are only ever written from valid `&str`s
+        unsafe { core::str::from_utf8_unchecked(&self.buff) }
+    }
+
+    /// Fetch the string contents as bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buff
+    }
+
+    /// Convert into the underlying byte buffer
+    pub fn into_bytes(self) -> SmallVec<[u8; N]> {
+        self.buff
+    }
+
+    /// Convert into a [`String`], without copying if the contents have spilled to the heap
+    pub fn into_string(self) -> String {
+        // SAFETY: contents are only ever written from valid `&str`s
+        unsafe { String::from_utf8_unchecked(self.buff.into_vec()) }
+    }
+
+    /// Fetch the string length in bytes
+    pub fn len(&self) -> usize {
+        self.buff.len()
+    }
+
+    /// Check whether the string is empty
+    pub fn is_empty(&self) -> bool {
+        self.buff.is_empty()
+    }
+
+    /// Check whether the string contents have spilled to the heap
+    pub fn spilled(&self) -> bool {
+        self.buff.spilled()
+    }
+
+    /// Append a string slice
+    pub fn push_str(&mut self, s: &str) {
+        self.buff.extend_from_slice(s.as_bytes())
+    }
+
+    /// Append a character
+    pub fn push(&mut self, c: char) {
+        self.push_str(c.encode_utf8(&mut [0u8; 4]))
+    }
+
+    /// Remove all contents from the string
+    pub fn clear(&mut self) {
+        self.buff.clear()
+    }
+}
+
+impl <const N: usize> From<&str> for InlineString<N> {
+    fn from(s: &str) -> Self {
+        Self { buff: SmallVec::from_slice(s.as_bytes()) }
+    }
+}
+
+/// Conversion from [`String`] keeps the existing allocation
+impl <const N: usize> From<String> for InlineString<N> {
+    fn from(s: String) -> Self {
+        Self { buff: SmallVec::from_vec(s.into_bytes()) }
+    }
+}
+
+impl <const N: usize> Deref for InlineString<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl <const N: usize> AsRef<str> for InlineString<N> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl <const N: usize> AsRef<[u8]> for InlineString<N> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl <const N: usize> Borrow<str> for InlineString<N> {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl <const N: usize> Debug for InlineString<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl <const N: usize> Display for InlineString<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+impl <const N: usize> Write for InlineString<N> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+}
+
+impl <const N: usize, const M: usize> PartialEq<InlineString<M>> for InlineString<N> {
+    fn eq(&self, other: &InlineString<M>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl <const N: usize> Eq for InlineString<N> {}
+
+impl <const N: usize> PartialEq<str> for InlineString<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl <'a, const N: usize> PartialEq<&'a str> for InlineString<N> {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl <const N: usize> PartialOrd for InlineString<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl <const N: usize> Ord for InlineString<N> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl <const N: usize> Hash for InlineString<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+/// Conversion into owned storage avoids a copy where contents have spilled to the heap
+impl <const N: usize> IntoOwned for Inline<N> {
+    fn into_owned_list<T: Debug + Clone>(list: Self::List<T>) -> Vec<T> {
+        list.into_vec()
+    }
+
+    fn into_owned_string(string: Self::String) -> String {
+        string.into_string()
+    }
+
+    fn into_owned_bytes(bytes: Self::Bytes) -> Vec<u8> {
+        bytes.into_vec()
+    }
+}
+
+/// Conversion from owned storage keeps existing allocations
+impl <const N: usize> FromOwned for Inline<N> {
+    fn from_owned_list<T: Debug>(list: Vec<T>) -> Self::List<T> {
+        SmallVec::from_vec(list)
+    }
+
+    fn from_owned_string(string: String) -> Self::String {
+        InlineString::from(string)
+    }
+
+    fn from_owned_bytes(bytes: Vec<u8>) -> Self::Bytes {
+        SmallVec::from_vec(bytes)
+    }
+}
+
+impl <const N: usize> StorMut for Inline<N> {
+    fn new_list<T: Debug>() -> Self::List<T> {
+        SmallVec::new()
+    }
+
+    fn new_string() -> Self::String {
+        InlineString::new()
+    }
+
+    fn new_bytes() -> Self::Bytes {
+        SmallVec::new()
+    }
+
+    fn try_push<T: Debug>(list: &mut Self::List<T>, value: T) -> Result<(), CapacityError> {
+        list.push(value);
+        Ok(())
+    }
+
+    fn extend<T: Debug, I: IntoIterator<Item = T>>(list: &mut Self::List<T>, iter: I) -> Result<(), CapacityError> {
+        list.extend(iter);
+        Ok(())
+    }
+
+    fn clear<T: Debug>(list: &mut Self::List<T>) {
+        list.clear()
+    }
+
+    fn push_str(string: &mut Self::String, value: &str) -> Result<(), CapacityError> {
+        string.push_str(value);
+        Ok(())
+    }
+
+    fn extend_from_slice(bytes: &mut Self::Bytes, value: &[u8]) -> Result<(), CapacityError> {
+        bytes.extend_from_slice(value);
+        Ok(())
+    }
+}
+
+impl <'a, const N: usize> TryFromStor<'a> for Inline<N> {
+    fn try_list_from<T: Debug + Clone>(v: &'a [T]) -> Result<Self::List<T>, StorError> {
+        Ok(SmallVec::from(v))
+    }
+
+    fn try_string_from(v: &'a str) -> Result<Self::String, StorError> {
+        Ok(InlineString::from(v))
+    }
+
+    fn try_bytes_from(v: &'a [u8]) -> Result<Self::Bytes, StorError> {
+        Ok(SmallVec::from_slice(v))
+    }
+}
+
+impl <'a, const N: usize> StorDecode<'a> for Inline<N> {
+    fn decode_list<T: Debug + Decode<'a>>(d: &mut Decoder<'a>) -> Result<Self::List<T>, CodecError> {
+        let mut l = SmallVec::new();
+        d.decode_list(|v| {
+            l.push(v);
+            Ok(())
+        })?;
+        Ok(l)
+    }
+}
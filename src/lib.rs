@@ -1,32 +1,83 @@
 //! [`Stor`] trait defines underlying storage for use in type definitions
 //! 
 //! ```
-//! use stor::Stor;
+//! use stor::{Stor, RefList, ConstString};
 //! 
 //! /// Generic object over storage types
-//! enum Something<S: Stor<()>> {
-//!   List(S::List),
+//! enum Something<S: Stor> {
+//!   List(S::List<u32>),
+//!   Headers(S::List<Header<S>>),
 //!   Str(S::String),
 //!   Bytes(S::Bytes),
 //! }
 //! 
+//! /// Nested object using the same storage
+//! #[derive(Debug)]
+//! struct Header<S: Stor> {
+//!   name: S::String,
+//!   value: S::Bytes,
+//! }
+//! 
 //! /// Owned version (requires `alloc` feature)
 //! type SomethingOwned = Something<stor::Owned>;
 //! let _ = SomethingOwned::Str("hello".to_string());
 //! let _ = SomethingOwned::Bytes(vec![0xaa, 0xbb, 0xcc]);
+//! let _ = SomethingOwned::Headers(vec![Header{ name: "a".to_string(), value: vec![0xaa] }]);
 //! 
 //! /// Reference version
 //! type SomethingRef<'a> = Something<stor::Ref<'a>>;
 //! let _ = SomethingRef::Str("hello");
 //! let _ = SomethingRef::Bytes(&[0xaa, 0xbb, 0xcc]);
+//! let _ = SomethingRef::List(RefList::new(&[1, 2, 3]));
 //! 
 //! /// Const N version
 //! type SomethingConst<'a> = Something<stor::Const<3>>;
-//! let _ = SomethingConst::Str("hello");
+//! let _ = SomethingConst::Str(ConstString::from_str_const("hey"));
 //! let _ = SomethingConst::Bytes([0xaa, 0xbb, 0xcc]);
+//! let _ = SomethingConst::List([1, 2, 3]);
 //! ```
 //! 
+//! With the `derive` feature, `#[derive(StorConvert)]` generates `to_owned_stor()`
+//! and `as_ref_stor()` methods to convert between these instantiations, and
+//! `#[derive(StorPartialEq)]` compares instantiations by content using [`cmp`].
+//! 
+//! With the `serde` feature, markers and containers implement `Serialize` and `Deserialize`,
+//! so `#[derive(Serialize, Deserialize)]` works on types generic over [`Stor`] without
+//! additional bounds, and [`Ref`] instantiations borrow strings and bytes from the input.
+//! 
+//! With the `arrayvec` feature, the `ArrayVec<N>` marker uses `arrayvec` containers in the
+//! same manner as `Heapless<N>`, and with the `tinyvec` feature the `TinyVec<N>` marker uses
+//! `tinyvec` byte buffers (with lists and strings as for [`Bounded`]).
+//! 
+//! Containers hash consistently by content across markers, see [`hash`] for helpers
+//! and `hash32` support (with the `hash32` feature) for `heapless` maps.
+//! 
+//! With the `bumpalo` feature, the `Bump` marker allocates containers from a `bumpalo`
+//! arena, and `#[derive(StorConvert)]` also generates `to_bump_stor()` to copy objects into an arena.
+//! 
+//! With the `slab` feature, the `Slab` marker allocates containers from a single caller-provided
+//! `SlabBuffer`, and `#[derive(StorConvert)]` also generates `to_slab_stor()` to copy objects into the buffer.
+//! 
+//! With the `pool` feature, the `Pooled` marker allocates each container from a fixed block
+//! `BlockPool`, which may be `static` so containers can be passed between tasks without copying.
+//! 
+//! With the `bytes` feature, the `Buf` marker shares `bytes::Bytes` buffers, and
+//! `#[derive(StorConvert)]` also generates `to_buf_stor()` to slice borrowed objects from a parent buffer.
+//! 
+//! With the `smallvec` feature, the `Inline<N>` marker holds short contents inline and
+//! spills longer contents to the heap.
+//! 
+//! With the `smol_str` or `compact_str` features, the `Smol` and `Compact` markers use
+//! `Owned` lists and bytes with compact string types that avoid allocating for short strings.
+//! 
+//! With the `allocator_api` feature (requires nightly), the `OwnedIn<A>` marker uses
+//! containers with a custom allocator.
+//! 
+//! With the `defmt` feature, markers and containers implement `defmt::Format`, and
+//! `StorFormat` bounds string and byte containers for use in generic code.
+//! 
 #![no_std]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 
 use core::fmt::Debug;
 use core::marker::PhantomData;
@@ -34,10 +85,121 @@ use core::marker::PhantomData;
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+mod error;
+pub use error::{CapacityError, StorError};
+
+mod stor_mut;
+pub use stor_mut::StorMut;
+
+mod try_from;
+pub use try_from::TryFromStor;
+
+mod bounds;
+pub use bounds::{StorClone, StorEq, StorHash, StorOrd, StorBorrow};
+
+mod ref_list;
+pub use ref_list::RefList;
+
+mod const_string;
+pub use const_string::ConstString;
+
+mod bounded_vec;
+pub use bounded_vec::BoundedVec;
+
+#[cfg(feature = "alloc")]
+mod owned;
+#[cfg(feature = "alloc")]
+pub use owned::{IntoOwned, FromOwned};
+
+#[cfg(feature = "alloc")]
+mod cow;
+#[cfg(feature = "alloc")]
+pub use cow::{Cow, CowList};
+
+#[cfg(feature = "slab")]
+mod slab;
+#[cfg(feature = "slab")]
+pub use slab::{Slab, SlabBuffer, SlabList, SlabString};
+
+#[cfg(all(feature = "pool", target_has_atomic = "8"))]
+mod pool;
+#[cfg(all(feature = "pool", target_has_atomic = "8"))]
+pub use pool::{Pooled, BlockPool, PoolList, PoolString};
+
+#[cfg(feature = "smallvec")]
+mod inline;
+#[cfg(feature = "smallvec")]
+pub use inline::{Inline, InlineString};
+
+#[cfg(any(feature = "smol_str", feature = "compact_str"))]
+mod owned_string;
+
+#[cfg(feature = "smol_str")]
+mod smol;
+#[cfg(feature = "smol_str")]
+pub use smol::Smol;
+
+#[cfg(feature = "compact_str")]
+mod compact;
+#[cfg(feature = "compact_str")]
+pub use compact::Compact;
+
+#[cfg(feature = "allocator_api")]
+mod owned_in;
+#[cfg(feature = "allocator_api")]
+pub use owned_in::{OwnedIn, StringIn};
+
+#[cfg(feature = "bumpalo")]
+mod bump;
+#[cfg(feature = "bumpalo")]
+pub use bump::{Bump, BumpList};
+
+#[doc(hidden)]
+#[cfg(feature = "bumpalo")]
+pub use bumpalo;
+
+#[cfg(all(feature = "bytes", target_has_atomic = "ptr"))]
+mod buf;
+#[cfg(all(feature = "bytes", target_has_atomic = "ptr"))]
+pub use buf::{Buf, BufString};
+
+#[doc(hidden)]
+#[cfg(all(feature = "bytes", target_has_atomic = "ptr"))]
+pub use bytes;
+
+#[cfg(feature = "serde")]
+mod serde_impl;
+
+#[cfg(feature = "defmt")]
+mod defmt_impl;
+#[cfg(feature = "defmt")]
+pub use defmt_impl::StorFormat;
+
+pub mod codec;
+
+pub mod cmp;
+
+pub mod hash;
+
+/// Derive conversions between [`Owned`] and [`Ref`] instantiations of a type (requires `derive` feature)
+#[cfg(feature = "derive")]
+pub use stor_derive::StorConvert;
+
+/// Derive [`PartialEq`] between instantiations of a type over different markers (requires `derive` feature)
+#[cfg(feature = "derive")]
+pub use stor_derive::StorPartialEq;
+
+mod derive;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::derive::*;
+}
+
 /// [`Stor`] trait provides abstract container types
-pub trait Stor<Inner: Debug = ()>: Debug {
-    /// Type for holding lists of Inner objects
-    type List: AsRef<[Inner]> + Debug;
+pub trait Stor: Debug {
+    /// Type for holding lists of `T` objects
+    type List<T: Debug>: AsRef<[T]> + Debug;
     /// Type for holding strings
     type String: AsRef<str> + Debug;
     /// Type for holding bytes
@@ -46,49 +208,887 @@ pub trait Stor<Inner: Debug = ()>: Debug {
 
 /// Owned marker uses [`alloc`] backed storage
 #[cfg(feature = "alloc")]
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Owned;
 
 #[cfg(feature = "alloc")]
-impl <T: Clone + Debug> Stor<T> for Owned {
-    type List = alloc::vec::Vec<T>;
+impl Stor for Owned {
+    type List<T: Debug> = alloc::vec::Vec<T>;
     type String = alloc::string::String;
     type Bytes = alloc::vec::Vec<u8>;
 }
 
-/// Ref marker uses `&'a T` containers
-#[derive(Copy, Clone, Debug, PartialEq)]
+/// Boxed marker uses [`alloc::boxed::Box`] backed storage, without spare capacity
+#[cfg(feature = "alloc")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Boxed;
+
+#[cfg(feature = "alloc")]
+impl Stor for Boxed {
+    type List<T: Debug> = alloc::boxed::Box<[T]>;
+    type String = alloc::boxed::Box<str>;
+    type Bytes = alloc::boxed::Box<[u8]>;
+}
+
+/// Shared marker uses [`alloc::sync::Arc`] backed storage for cheap cloning across threads
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Shared;
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl Stor for Shared {
+    type List<T: Debug> = alloc::sync::Arc<[T]>;
+    type String = alloc::sync::Arc<str>;
+    type Bytes = alloc::sync::Arc<[u8]>;
+}
+
+/// Local marker uses [`alloc::rc::Rc`] backed storage for cheap cloning within a thread
+#[cfg(feature = "alloc")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Local;
+
+#[cfg(feature = "alloc")]
+impl Stor for Local {
+    type List<T: Debug> = alloc::rc::Rc<[T]>;
+    type String = alloc::rc::Rc<str>;
+    type Bytes = alloc::rc::Rc<[u8]>;
+}
+
+/// Ref marker uses `&'a T` containers, with [`RefList`] standing in for `&'a [T]`
+///
+/// With the `serde` feature, deserialising borrows strings and bytes from the input. Lists
+/// cannot be borrowed, so while types containing lists still derive `Deserialize`, this
+/// fails at runtime for any input containing a list; use an owned marker for these.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Ref<'a> (PhantomData<&'a ()>);
 
-impl <'a, T: Clone + Debug + 'a> Stor<T> for Ref<'a> {
-    type List = &'a [T];
+impl <'a> Stor for Ref<'a> {
+    type List<T: Debug> = RefList<'a, T>;
     type String = &'a str;
     type Bytes = &'a [u8];
 }
 
-/// Const marker uses const size containers
-#[derive(Copy, Clone, Debug, PartialEq)]
+/// Const marker uses const size containers, with [`ConstString`] for strings of up to `N` bytes
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Const<const N: usize>;
 
-impl <T: Clone + Debug, const N: usize> Stor<T> for Const<N> {
-    type List = [T; N];
-    type String = &'static str;
+impl <const N: usize> Stor for Const<N> {
+    type List<T: Debug> = [T; N];
+    type String = ConstString<N>;
     type Bytes = [u8; N];
 }
 
-/// Heapless marker uses [`heapless`] containers
+/// Bounded marker uses [`BoundedVec`] and [`ConstString`] containers holding up to `N` items,
+/// without requiring `heapless`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Bounded<const N: usize>;
+
+impl <const N: usize> Stor for Bounded<N> {
+    type List<T: Debug> = BoundedVec<T, N>;
+    type String = ConstString<N>;
+    type Bytes = BoundedVec<u8, N>;
+}
+
+/// Heapless marker uses [`heapless`] containers with capacity `N`
+#[cfg(feature = "heapless")]
+pub type Heapless<const N: usize> = HeaplessSized<N, N, N>;
+
+/// HeaplessSized marker uses [`heapless`] containers with per-container capacities,
+/// `L` items for lists, `S` bytes for strings and `B` bytes for byte buffers
+///
+/// ```
+/// use stor::{Stor, HeaplessSized};
+///
+/// struct Message<S: Stor> {
+///     headers: S::List<u16>,
+///     payload: S::Bytes,
+/// }
+///
+/// // 4 headers alongside up to 256 bytes of payload
+/// type MessageHeapless = Message<HeaplessSized<4, 0, 256>>;
+/// ```
 #[cfg(feature = "heapless")]
-#[derive(Copy, Clone, Debug, PartialEq)]
-pub struct Heapless<const N: usize>;
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HeaplessSized<const L: usize, const S: usize, const B: usize>;
 
 #[cfg(feature = "heapless")]
-impl <T: Clone + Debug, const N: usize> Stor<T> for Heapless<N> {
-    type List = heapless::Vec<T, N>;
-    type String = heapless::String<N>;
-    type Bytes = heapless::Vec<u8, N>;
+impl <const L: usize, const S: usize, const B: usize> Stor for HeaplessSized<L, S, B> {
+    type List<T: Debug> = heapless::Vec<T, L>;
+    type String = heapless::String<S>;
+    type Bytes = heapless::Vec<u8, B>;
+}
+
+/// ArrayVec marker uses [`arrayvec`] containers with capacity `N`
+#[cfg(feature = "arrayvec")]
+pub type ArrayVec<const N: usize> = ArrayVecSized<N, N, N>;
+
+/// ArrayVecSized marker uses [`arrayvec`] containers with per-container capacities,
+/// `L` items for lists, `S` bytes for strings and `B` bytes for byte buffers
+///
+/// ```
+/// use stor::{Stor, ArrayVec, TryFromStor};
+///
+/// struct Header<S: Stor> {
+///     name: S::String,
+///     value: S::Bytes,
+/// }
+///
+/// let h = Header::<ArrayVec<4>> {
+///     name: ArrayVec::try_string_from("abc").unwrap(),
+///     value: ArrayVec::try_bytes_from(&[0xaa, 0xbb]).unwrap(),
+/// };
+/// assert_eq!(&h.name, "abc");
+/// assert!(ArrayVec::<4>::try_bytes_from(&[0; 5]).is_err());
+/// ```
+#[cfg(feature = "arrayvec")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ArrayVecSized<const L: usize, const S: usize, const B: usize>;
+
+#[cfg(feature = "arrayvec")]
+impl <const L: usize, const S: usize, const B: usize> Stor for ArrayVecSized<L, S, B> {
+    type List<T: Debug> = arrayvec::ArrayVec<T, L>;
+    type String = arrayvec::ArrayString<S>;
+    type Bytes = arrayvec::ArrayVec<u8, B>;
+}
+
+/// TinyVec marker uses [`tinyvec`] byte buffers with capacity `N`, see [`TinyVecSized`]
+#[cfg(feature = "tinyvec")]
+pub type TinyVec<const N: usize> = TinyVecSized<N, N, N>;
+
+/// TinyVecSized marker uses [`tinyvec::ArrayVec`] byte buffers with per-container capacities,
+/// `L` items for lists, `S` bytes for strings and `B` bytes for byte buffers
+///
+/// Only byte buffers are `tinyvec` containers. `tinyvec` requires items to implement
+/// [`Default`], which [`Stor::List`] cannot require, and does not provide a string type,
+/// so lists use [`BoundedVec`] and strings use [`ConstString`] exactly as for [`Bounded`].
+///
+/// ```
+/// use stor::{Stor, TinyVec, TryFromStor};
+///
+/// struct Header<S: Stor> {
+///     name: S::String,
+///     value: S::Bytes,
+/// }
+///
+/// let h = Header::<TinyVec<4>> {
+///     name: TinyVec::try_string_from("abc").unwrap(),
+///     value: TinyVec::try_bytes_from(&[0xaa, 0xbb]).unwrap(),
+/// };
+/// assert_eq!(h.value, tinyvec::array_vec!([u8; 4] => 0xaa, 0xbb));
+/// ```
+#[cfg(feature = "tinyvec")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TinyVecSized<const L: usize, const S: usize, const B: usize>;
+
+#[cfg(feature = "tinyvec")]
+impl <const L: usize, const S: usize, const B: usize> Stor for TinyVecSized<L, S, B> {
+    type List<T: Debug> = BoundedVec<T, L>;
+    type String = ConstString<S>;
+    type Bytes = tinyvec::ArrayVec<[u8; B]>;
 }
 
 #[cfg(test)]
 mod tests {
-    // TODO: write some tests
+    extern crate std;
+
+    use core::hash::Hash;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct Fields<S: Stor> {
+        list: S::List<u16>,
+        string: S::String,
+        bytes: S::Bytes,
+    }
+
+    fn fill<S: StorMut>() -> Result<Fields<S>, CapacityError> {
+        let mut list = S::new_list();
+        S::extend(&mut list, [1, 2, 3])?;
+        S::try_push(&mut list, 4)?;
+
+        let mut string = S::new_string();
+        S::push_str(&mut string, "abcd")?;
+
+        let mut bytes = S::new_bytes();
+        S::extend_from_slice(&mut bytes, &[0xaa, 0xbb, 0xcc, 0xdd])?;
+
+        Ok(Fields{ list, string, bytes })
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn stor_mut_owned() {
+        let mut f = fill::<Owned>().unwrap();
+        assert_eq!(f.list, [1, 2, 3, 4]);
+        assert_eq!(f.string, "abcd");
+        assert_eq!(f.bytes, [0xaa, 0xbb, 0xcc, 0xdd]);
+
+        Owned::clear(&mut f.list);
+        assert!(f.list.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "heapless")]
+    fn stor_mut_heapless() {
+        let f = fill::<Heapless<4>>().unwrap();
+        assert_eq!(f.list, [1, 2, 3, 4]);
+        assert_eq!(f.string, "abcd");
+        assert_eq!(f.bytes, [0xaa, 0xbb, 0xcc, 0xdd]);
+
+        assert_eq!(fill::<Heapless<3>>().unwrap_err(), CapacityError);
+
+        assert!(fill::<HeaplessSized<4, 4, 4>>().is_ok());
+        assert_eq!(fill::<HeaplessSized<4, 3, 4>>().unwrap_err(), CapacityError);
+        assert_eq!(fill::<HeaplessSized<4, 4, 3>>().unwrap_err(), CapacityError);
+    }
+
+    #[test]
+    #[cfg(feature = "arrayvec")]
+    fn stor_mut_arrayvec() {
+        let f = fill::<ArrayVec<4>>().unwrap();
+        assert_eq!(&f.list[..], [1, 2, 3, 4]);
+        assert_eq!(&f.string, "abcd");
+        assert_eq!(&f.bytes[..], [0xaa, 0xbb, 0xcc, 0xdd]);
+
+        assert_eq!(fill::<ArrayVec<3>>().unwrap_err(), CapacityError);
+        assert_eq!(fill::<ArrayVecSized<4, 3, 4>>().unwrap_err(), CapacityError);
+        assert_eq!(fill::<ArrayVecSized<4, 4, 3>>().unwrap_err(), CapacityError);
+
+        assert!(try_fields::<ArrayVecSized<2, 1, 3>>(&[1, 2], b"a", &[1, 2, 3]).is_ok());
+        assert_eq!(try_fields::<ArrayVecSized<2, 1, 3>>(&[1, 2], b"ab", &[1]).unwrap_err(), StorError::CapacityOverflow);
+    }
+
+    #[test]
+    #[cfg(feature = "tinyvec")]
+    fn stor_mut_tinyvec() {
+        let f = fill::<TinyVec<4>>().unwrap();
+        assert_eq!(f.list, [1, 2, 3, 4]);
+        assert_eq!(f.string, "abcd");
+        assert_eq!(&f.bytes[..], [0xaa, 0xbb, 0xcc, 0xdd]);
+
+        assert_eq!(fill::<TinyVec<3>>().unwrap_err(), CapacityError);
+        assert_eq!(fill::<TinyVecSized<4, 4, 3>>().unwrap_err(), CapacityError);
+
+        assert!(try_fields::<TinyVecSized<2, 1, 3>>(&[1, 2], b"a", &[1, 2, 3]).is_ok());
+        assert_eq!(try_fields::<TinyVecSized<2, 1, 3>>(&[1, 2], b"a", &[1, 2, 3, 4]).unwrap_err(), StorError::CapacityOverflow);
+    }
+
+    #[test]
+    fn ref_list() {
+        let data = [1u16, 2, 3];
+        let l: <Ref<'_> as Stor>::List<u16> = RefList::new(&data);
+
+        assert_eq!(l, [1, 2, 3]);
+        assert_eq!(l.len(), 3);
+        assert_eq!(l.into_slice(), &data);
+        assert_eq!(l.into_iter().sum::<u16>(), 6);
+        assert!(RefList::<u16>::default().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn cow_patch() {
+        let data = [1u16, 2, 3];
+        let mut c: Fields<Cow> = Fields {
+            list: CowList::from(&data[..]),
+            string: "a\\nb".into(),
+            bytes: (&[0xaa][..]).into(),
+        };
+        assert!(c.list.is_borrowed());
+
+        c.string = c.string.replace("\\n", "\n").into();
+        c.list.to_mut().push(4);
+
+        assert!(c.list.is_owned());
+        assert_eq!(Cow::into_owned_list(c.list), [1, 2, 3, 4]);
+        assert_eq!(Cow::into_owned_string(c.string), "a\nb");
+        assert_eq!(Cow::into_owned_bytes(c.bytes), [0xaa]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn shared_clone() {
+        let s: Fields<Shared> = Fields {
+            list: std::vec![1u16, 2, 3].into(),
+            string: "abc".into(),
+            bytes: std::vec![0xaa, 0xbb].into(),
+        };
+
+        let c = std::thread::spawn({
+            let list = s.list.clone();
+            move || list.iter().sum::<u16>()
+        });
+        assert_eq!(c.join().unwrap(), 6);
+
+        let l = alloc::rc::Rc::<[u16]>::from(&s.list[..]);
+        let f: Fields<Local> = Fields { list: l.clone(), string: "abc".into(), bytes: s.bytes.as_ref().into() };
+        assert!(alloc::rc::Rc::ptr_eq(&f.list, &l));
+        assert_eq!(Local::into_owned_list(f.list), Shared::into_owned_list(s.list));
+    }
+
+    #[test]
+    fn const_string() {
+        const S: ConstString<4> = ConstString::from_str_const("ab");
+        let mut s: <Const<4> as Stor>::String = S;
+
+        s.push('é').unwrap();
+        assert_eq!(s, "abé");
+        assert_eq!(s.len(), 4);
+        assert_eq!(s.push('c'), Err(CapacityError));
+
+        assert_eq!(ConstString::<2>::try_from_str("abc"), Err(CapacityError));
+        assert_eq!(ConstString::<8>::try_from("abé").unwrap(), s);
+
+        s.truncate(2);
+        assert_eq!(s, "ab");
+    }
+
+    #[test]
+    fn stor_mut_bounded() {
+        let f = fill::<Bounded<4>>().unwrap();
+        assert_eq!(f.list, [1, 2, 3, 4]);
+        assert_eq!(f.string, "abcd");
+        assert_eq!(f.bytes, [0xaa, 0xbb, 0xcc, 0xdd]);
+
+        assert_eq!(fill::<Bounded<3>>().unwrap_err(), CapacityError);
+    }
+
+    #[test]
+    fn bounded_vec_drop() {
+        let r = std::rc::Rc::new(());
+
+        let mut v = BoundedVec::<_, 3>::new();
+        v.push(r.clone()).unwrap();
+        v.push(r.clone()).unwrap();
+        assert_eq!(std::rc::Rc::strong_count(&r), 3);
+
+        drop(v.pop());
+        assert_eq!(std::rc::Rc::strong_count(&r), 2);
+
+        drop(v);
+        assert_eq!(std::rc::Rc::strong_count(&r), 1);
+    }
+
+    fn try_fields<'a, S: TryFromStor<'a>>(list: &'a [u16], string: &'a [u8], bytes: &'a [u8]) -> Result<Fields<S>, StorError> {
+        Ok(Fields {
+            list: S::try_list_from(list)?,
+            string: S::try_string_from_utf8(string)?,
+            bytes: S::try_bytes_from(bytes)?,
+        })
+    }
+
+    #[test]
+    fn try_from_stor() {
+        assert!(try_fields::<Ref>(&[1, 2], b"ab", &[1, 2]).is_ok());
+        assert!(try_fields::<Const<2>>(&[1, 2], b"ab", &[1, 2]).is_ok());
+        assert!(try_fields::<Bounded<2>>(&[1], b"a", &[1]).is_ok());
+
+        assert_eq!(try_fields::<Ref>(&[1], &[0xff], &[1]).unwrap_err(), StorError::InvalidUtf8);
+        assert_eq!(try_fields::<Bounded<2>>(&[1, 2, 3], b"a", &[1]).unwrap_err(), StorError::CapacityOverflow);
+        assert_eq!(
+            try_fields::<Const<2>>(&[1, 2], b"ab", &[1]).unwrap_err(),
+            StorError::LengthMismatch{ expected: 2, actual: 1 },
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn try_from_stor_alloc() {
+        let f = try_fields::<Owned>(&[1, 2], b"ab", &[1, 2]).unwrap();
+        assert_eq!(f.list, [1, 2]);
+
+        assert!(try_fields::<Boxed>(&[1, 2], b"ab", &[1, 2]).is_ok());
+        assert!(try_fields::<Shared>(&[1, 2], b"ab", &[1, 2]).is_ok());
+        assert!(try_fields::<Local>(&[1, 2], b"ab", &[1, 2]).is_ok());
+        assert!(try_fields::<Cow>(&[1, 2], b"ab", &[1, 2]).unwrap().list.is_borrowed());
+    }
+
+    #[test]
+    #[cfg(feature = "heapless")]
+    fn try_from_stor_heapless() {
+        assert!(try_fields::<HeaplessSized<2, 1, 3>>(&[1, 2], b"a", &[1, 2, 3]).is_ok());
+        assert_eq!(try_fields::<HeaplessSized<2, 1, 3>>(&[1, 2], b"ab", &[1]).unwrap_err(), StorError::CapacityOverflow);
+    }
+
+    #[test]
+    fn codec_primitives() {
+        let mut buff = [0u8; 16];
+
+        let mut e = codec::Encoder::new(&mut buff);
+        e.encode(&0x0102u16).unwrap();
+        e.encode_len(300).unwrap();
+        e.encode(&Some(true)).unwrap();
+        e.encode_str("ab").unwrap();
+        let n = e.len();
+        assert_eq!(&buff[..n], &[0x02, 0x01, 0xac, 0x02, 0x01, 0x01, 0x02, b'a', b'b']);
+
+        let mut d = codec::Decoder::new(&buff[..n]);
+        assert_eq!(d.decode::<u16>(), Ok(0x0102));
+        assert_eq!(d.decode_len(), Ok(300));
+        assert_eq!(d.decode::<Option<bool>>(), Ok(Some(true)));
+        assert_eq!(d.decode_str(), Ok("ab"));
+        assert_eq!(d.decode::<u8>(), Err(codec::CodecError::BufferTooShort));
+
+        assert_eq!(codec::encode(&0u32, &mut buff[..2]), Err(codec::CodecError::BufferTooShort));
+    }
+
+    #[test]
+    fn codec_lists() {
+        use codec::{CodecError, StorDecode};
+
+        let mut buff = [0u8; 16];
+        let mut e = codec::Encoder::new(&mut buff);
+        e.encode_list(&[1u16, 2, 3]).unwrap();
+        let n = e.len();
+
+        let list = |b| <Bounded<3> as StorDecode>::decode_list::<u16>(&mut codec::Decoder::new(b));
+        assert_eq!(list(&buff[..n]).unwrap(), [1, 2, 3]);
+
+        let mut d = codec::Decoder::new(&buff[..n]);
+        assert_eq!(<Const<3> as StorDecode>::decode_list::<u16>(&mut d), Ok([1, 2, 3]));
+
+        let mut d = codec::Decoder::new(&buff[..n]);
+        assert_eq!(<Bounded<2> as StorDecode>::decode_list::<u16>(&mut d).unwrap_err(), CodecError::Stor(StorError::CapacityOverflow));
+
+        let mut d = codec::Decoder::new(&buff[..n]);
+        assert_eq!(
+            <Const<4> as StorDecode>::decode_list::<u16>(&mut d),
+            Err(CodecError::Stor(StorError::LengthMismatch{ expected: 4, actual: 3 })),
+        );
+
+        let mut d = codec::Decoder::new(&buff[..n]);
+        assert_eq!(
+            <Const<2> as StorDecode>::decode_list::<u16>(&mut d),
+            Err(CodecError::Stor(StorError::LengthMismatch{ expected: 2, actual: 3 })),
+        );
+
+        let mut d = codec::Decoder::new(&buff[..n]);
+        assert_eq!(<Ref as StorDecode>::decode_list::<u16>(&mut d).unwrap_err(), CodecError::Unsupported);
+    }
+
+    #[test]
+    fn codec_list_length() {
+        use codec::{CodecError, Decode, Decoder};
+
+        #[derive(Debug, PartialEq)]
+        struct Unit;
+
+        impl <'a> Decode<'a> for Unit {
+            fn decode(_d: &mut Decoder<'a>) -> Result<Self, CodecError> {
+                Ok(Unit)
+            }
+        }
+
+        // Lengths beyond the remaining buffer are rejected without decoding items
+        let b = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01];
+        let r = Decoder::new(&b).decode_list(|_: Unit| Ok(()));
+        assert_eq!(r, Err(CodecError::BufferTooShort));
+
+        let r = Decoder::new(&[3, 1, 2]).decode_list(|_: u8| Ok(()));
+        assert_eq!(r, Err(CodecError::BufferTooShort));
+    }
+
+    #[test]
+    #[cfg(feature = "defmt")]
+    fn defmt_markers() {
+        fn is_format<S: StorFormat>() where S::List<u8>: defmt::Format {}
+
+        is_format::<Ref>();
+        is_format::<Const<2>>();
+        is_format::<Bounded<2>>();
+        #[cfg(feature = "alloc")]
+        is_format::<Owned>();
+        #[cfg(feature = "alloc")]
+        is_format::<Cow>();
+        #[cfg(feature = "heapless")]
+        is_format::<Heapless<2>>();
+        #[cfg(feature = "tinyvec")]
+        is_format::<TinyVec<2>>();
+    }
+
+    fn is_bounded<S: StorClone + StorEq + StorHash + StorOrd>() where S::List<u8>: Clone + Eq + Hash + Ord {}
+
+    #[test]
+    fn bound_markers() {
+        is_bounded::<Ref>();
+        is_bounded::<Const<2>>();
+        is_bounded::<Bounded<2>>();
+        #[cfg(feature = "alloc")]
+        is_bounded::<Owned>();
+        #[cfg(feature = "alloc")]
+        is_bounded::<Boxed>();
+        #[cfg(feature = "alloc")]
+        is_bounded::<Shared>();
+        #[cfg(feature = "alloc")]
+        is_bounded::<Local>();
+        #[cfg(feature = "alloc")]
+        is_bounded::<Cow>();
+        #[cfg(feature = "heapless")]
+        is_bounded::<Heapless<2>>();
+        #[cfg(feature = "arrayvec")]
+        is_bounded::<ArrayVec<2>>();
+        #[cfg(feature = "tinyvec")]
+        is_bounded::<TinyVec<2>>();
+        #[cfg(feature = "smol_str")]
+        is_bounded::<Smol>();
+        #[cfg(feature = "compact_str")]
+        is_bounded::<Compact>();
+    }
+
+    fn content_hash<F: FnOnce(&mut std::collections::hash_map::DefaultHasher)>(f: F) -> u64 {
+        let mut h = std::collections::hash_map::DefaultHasher::new();
+        f(&mut h);
+        core::hash::Hasher::finish(&h)
+    }
+
+    #[test]
+    fn hash_consistency() {
+        let f = fill::<Bounded<4>>().unwrap();
+        let expected = [
+            content_hash(|h| hash::hash_list(&f.list, h)),
+            content_hash(|h| hash::hash_string(&f.string, h)),
+            content_hash(|h| hash::hash_bytes(&f.bytes, h)),
+        ];
+
+        // Container impls match content hashes
+        assert_eq!(content_hash(|h| f.list.hash(h)), expected[0]);
+        assert_eq!(content_hash(|h| f.string.hash(h)), expected[1]);
+        assert_eq!(content_hash(|h| f.bytes.hash(h)), expected[2]);
+
+        let r = Fields::<Ref>{ list: RefList::new(&[1, 2, 3, 4]), string: "abcd", bytes: &[0xaa, 0xbb, 0xcc, 0xdd] };
+        assert_eq!(content_hash(|h| r.list.hash(h)), expected[0]);
+        assert_eq!(content_hash(|h| r.string.hash(h)), expected[1]);
+        assert_eq!(content_hash(|h| r.bytes.hash(h)), expected[2]);
+
+        let c = try_fields::<Const<4>>(&[1, 2, 3, 4], b"abcd", &[0xaa, 0xbb, 0xcc, 0xdd]).unwrap();
+        assert_eq!(content_hash(|h| c.list.hash(h)), expected[0]);
+        assert_eq!(content_hash(|h| c.string.hash(h)), expected[1]);
+        assert_eq!(content_hash(|h| c.bytes.hash(h)), expected[2]);
+
+        #[cfg(feature = "alloc")]
+        {
+            let o = fill::<Owned>().unwrap();
+            assert_eq!(content_hash(|h| o.list.hash(h)), expected[0]);
+            assert_eq!(content_hash(|h| o.string.hash(h)), expected[1]);
+            assert_eq!(content_hash(|h| o.bytes.hash(h)), expected[2]);
+        }
+
+        #[cfg(feature = "heapless")]
+        {
+            let l = fill::<Heapless<4>>().unwrap();
+            assert_eq!(content_hash(|h| l.list.hash(h)), expected[0]);
+            assert_eq!(content_hash(|h| l.string.hash(h)), expected[1]);
+            assert_eq!(content_hash(|h| l.bytes.hash(h)), expected[2]);
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "hash32", feature = "heapless"))]
+    fn hash32_consistency() {
+        use hash32::{FnvHasher, Hasher};
+
+        let hash = |f: &dyn Fn(&mut FnvHasher)| {
+            let mut h = FnvHasher::default();
+            f(&mut h);
+            h.finish()
+        };
+
+        let l = fill::<Heapless<4>>().unwrap();
+        let b = fill::<Bounded<4>>().unwrap();
+        let c = try_fields::<Const<40>>(&[0; 40], &[b'a'; 40], &[0; 40]).unwrap();
+
+        assert_eq!(hash(&|h| hash32::Hash::hash(&l.list, h)), hash(&|h| hash32::Hash::hash(&b.list, h)));
+        assert_eq!(hash(&|h| hash32::Hash::hash(&l.string, h)), hash(&|h| hash32::Hash::hash(&b.string, h)));
+        assert_eq!(hash(&|h| hash32::Hash::hash(&l.bytes, h)), hash(&|h| hash::hash32_bytes(&b.bytes, h)));
+        assert_eq!(hash(&|h| hash32::Hash::hash(&RefList::new(&[0u16; 40]), h)), hash(&|h| hash::hash32_list(&c.list, h)));
+    }
+
+    fn borrow_lookup<'a, S: StorBorrow + TryFromStor<'a>>() {
+        let mut m = std::collections::BTreeMap::new();
+        m.insert(S::try_string_from("a").unwrap(), 1);
+        m.insert(S::try_string_from("b").unwrap(), 2);
+        assert_eq!(m.get("b"), Some(&2));
+
+        let mut s = std::collections::HashSet::new();
+        s.insert(S::try_bytes_from(&[1, 2]).unwrap());
+        assert!(s.contains(&[1u8, 2][..]));
+    }
+
+    #[test]
+    fn stor_borrow() {
+        borrow_lookup::<Ref>();
+        borrow_lookup::<Bounded<2>>();
+        #[cfg(feature = "alloc")]
+        borrow_lookup::<Owned>();
+        #[cfg(feature = "alloc")]
+        borrow_lookup::<Boxed>();
+        #[cfg(feature = "alloc")]
+        borrow_lookup::<Shared>();
+        #[cfg(feature = "alloc")]
+        borrow_lookup::<Local>();
+        #[cfg(feature = "alloc")]
+        borrow_lookup::<Cow>();
+        #[cfg(feature = "allocator_api")]
+        borrow_lookup::<OwnedIn<alloc::alloc::Global>>();
+    }
+
+    #[test]
+    #[cfg(feature = "bumpalo")]
+    fn stor_borrow_bump() {
+        fn borrowed<'a, S: StorBorrow>(s: &'a S::String, b: &'a S::Bytes) -> (&'a str, &'a [u8]) {
+            (core::borrow::Borrow::borrow(s), core::borrow::Borrow::borrow(b))
+        }
+
+        let arena = bumpalo::Bump::new();
+        let s = bumpalo::collections::String::from_str_in("a", &arena);
+        let b = bumpalo::vec![in &arena; 1u8, 2];
+        assert_eq!(borrowed::<Bump>(&s, &b), ("a", &[1u8, 2][..]));
+    }
+
+    #[test]
+    #[cfg(all(feature = "hash32", feature = "heapless"))]
+    fn stor_borrow_index_map() {
+        let mut m = heapless::FnvIndexMap::<<Bounded<4> as Stor>::String, u32, 4>::new();
+        m.insert(ConstString::from_str_const("abc"), 1).unwrap();
+        assert_eq!(m.get("abc"), Some(&1));
+    }
+
+    #[test]
+    #[cfg(feature = "bumpalo")]
+    fn bump_list_drop() {
+        let arena = bumpalo::Bump::new();
+        let r = std::rc::Rc::new(());
+
+        let l = BumpList::from_iter_in([r.clone(), r.clone()], &arena);
+        let c = l.clone();
+        assert_eq!(std::rc::Rc::strong_count(&r), 5);
+
+        drop(l);
+        drop(c);
+        assert_eq!(std::rc::Rc::strong_count(&r), 1);
+
+        let mut v = bumpalo::collections::Vec::new_in(&arena);
+        v.push(r.clone());
+        let l = BumpList::from(v);
+        assert_eq!(l.len(), 1);
+        drop(l);
+        assert_eq!(std::rc::Rc::strong_count(&r), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "allocator_api")]
+    fn owned_in() {
+        use alloc::alloc::Global;
+
+        let f = fill::<OwnedIn<Global>>().unwrap();
+        assert_eq!(f.list, [1, 2, 3, 4]);
+        assert_eq!(f.string, "abcd");
+
+        let t = try_fields::<OwnedIn<Global>>(&[1, 2], b"ab", &[1, 2]).unwrap();
+        assert_eq!(t.string, OwnedIn::string_in("ab", &Global));
+        assert_eq!(<OwnedIn<Global> as IntoOwned>::into_owned_list(t.list), [1, 2]);
+    }
+
+    #[test]
+    #[cfg(feature = "slab")]
+    fn slab_buffer() {
+        let mut scratch = [core::mem::MaybeUninit::<u32>::uninit(); 4];
+        let mut slab = SlabBuffer::from_uninit(&mut scratch);
+        assert_eq!(slab.capacity(), 16);
+
+        {
+            let b = slab.bytes_from(&[0xaa]).unwrap();
+            let l = slab.list_from(&[1u32, 2]).unwrap();
+            assert_eq!(b, [0xaa]);
+            assert_eq!(l, [1, 2]);
+
+            // Lists are aligned within the buffer
+            assert_eq!(l.as_ptr() as usize % core::mem::align_of::<u32>(), 0);
+            assert_eq!(slab.used(), 12);
+
+            let mut s = slab.string(4).unwrap();
+            assert_eq!(s.push_str("abcde"), Err(CapacityError));
+            s.push_str("abc").unwrap();
+            assert_eq!(s, "abc");
+
+            assert_eq!(slab.remaining(), 0);
+            assert_eq!(slab.bytes(1).unwrap_err(), CapacityError);
+            assert!(slab.list::<()>(8).is_ok());
+        }
+
+        slab.reset();
+        assert_eq!(slab.remaining(), 16);
+
+        let r = std::rc::Rc::new(());
+        let mut l = slab.list(2).unwrap();
+        l.push(r.clone()).unwrap();
+        l.push(r.clone()).unwrap();
+        assert!(l.push(r.clone()).is_err());
+        assert_eq!(std::rc::Rc::strong_count(&r), 3);
+
+        drop(l);
+        assert_eq!(std::rc::Rc::strong_count(&r), 1);
+    }
+
+    /// Reuses a buffer after writing padded items, run under Miri (`cargo +nightly miri test --features slab slab`)
+    /// to check that only initialised contents are ever read
+    #[test]
+    #[cfg(feature = "slab")]
+    fn slab_buffer_padding() {
+        let mut scratch = [core::mem::MaybeUninit::<u32>::uninit(); 4];
+        let mut slab = SlabBuffer::from_uninit(&mut scratch);
+
+        {
+            let l = slab.list_from(&[(1u8, 2u32), (3, 4)]).unwrap();
+            assert_eq!(l, [(1, 2), (3, 4)]);
+            assert_eq!(slab.remaining(), 0);
+        }
+
+        slab.reset();
+
+        let mut b = slab.bytes(8).unwrap();
+        assert!(b.is_empty());
+        b.extend_from_slice(&[0xaa; 3]).unwrap();
+        assert_eq!(b, [0xaa; 3]);
+
+        let s = slab.string_from("abc").unwrap();
+        assert_eq!(s.as_bytes(), b"abc");
+    }
+
+    #[test]
+    #[cfg(all(feature = "pool", target_has_atomic = "8"))]
+    fn block_pool() {
+        static POOL: BlockPool<8, 2> = BlockPool::new();
+
+        let mut l = POOL.list::<u16>().unwrap();
+        assert_eq!(l.capacity(), 4);
+        assert_eq!(l.extend_from_slice(&[1, 2, 3, 4, 5]), Err(CapacityError));
+        l.extend_from_slice(&[1, 2, 3, 4]).unwrap();
+        assert_eq!(l.push(5), Err(5));
+
+        // Containers can be moved between threads
+        let s = std::thread::spawn(|| POOL.string_from("abc").unwrap()).join().unwrap();
+        assert_eq!(s, "abc");
+
+        assert_eq!(POOL.available(), 0);
+        assert_eq!(POOL.bytes().unwrap_err(), CapacityError);
+        assert_eq!(POOL.list::<u128>().err(), Some(CapacityError));
+
+        drop(s);
+        assert_eq!(POOL.available(), 1);
+
+        let r = std::rc::Rc::new(());
+        let mut l = POOL.list().unwrap();
+        l.push(r.clone()).unwrap();
+        assert_eq!(std::rc::Rc::strong_count(&r), 2);
+
+        drop(l);
+        assert_eq!(std::rc::Rc::strong_count(&r), 1);
+        assert_eq!(POOL.available(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "smallvec")]
+    fn inline_spill() {
+        let f = fill::<Inline<2>>().unwrap();
+        assert_eq!(&f.list[..], [1, 2, 3, 4]);
+        assert!(f.string.spilled());
+
+        let t = try_fields::<Inline<4>>(&[1, 2], b"ab", &[1, 2]).unwrap();
+        assert_eq!(t.string, "ab");
+        assert!(!t.list.spilled() && !t.string.spilled());
+
+        // Spilled contents move to and from owned storage without copying
+        let o = alloc::string::String::from("abcdef");
+        let p = o.as_ptr();
+        let i = Inline::<4>::from_owned_string(o);
+        assert!(i.spilled());
+        let o = Inline::<4>::into_owned_string(i);
+        assert_eq!(o.as_ptr(), p);
+
+        let mut d = codec::Decoder::new(&[3, 1, 2, 3]);
+        assert_eq!(<Inline<2> as codec::StorDecode>::decode_list::<u8>(&mut d).unwrap()[..], [1, 2, 3]);
+    }
+
+    #[test]
+    #[cfg(all(feature = "bytes", target_has_atomic = "ptr"))]
+    fn buf_slices_parent() {
+        let parent = bytes::Bytes::from(alloc::vec![0x61, 0x62, 0xaa, 0xbb]);
+
+        // Borrowed contents are sliced from the parent
+        let b = Buf::bytes_in(&parent[2..], &parent);
+        assert_eq!(b.as_ptr(), parent[2..].as_ptr());
+        let s = Buf::string_in(core::str::from_utf8(&parent[..2]).unwrap(), &parent);
+        assert_eq!(s, "ab");
+        assert_eq!(s.as_ptr(), parent.as_ptr());
+
+        // Other contents are copied
+        let o = [0xaa, 0xbb];
+        let b = Buf::bytes_in(&o, &parent);
+        assert_ne!(b.as_ptr(), o.as_ptr());
+        assert_eq!(b, &o[..]);
+
+        let t = try_fields::<Buf>(&[1, 2], b"ab", &[1, 2]).unwrap();
+        assert_eq!(t.string, "ab");
+        assert_eq!(try_fields::<Buf>(&[1], &[0xff], &[1]).unwrap_err(), StorError::InvalidUtf8);
+
+        // Owned strings are moved without copying
+        let o = alloc::string::String::from("abc");
+        let p = o.as_ptr();
+        let s = Buf::from_owned_string(o);
+        assert_eq!(s.as_ptr(), p);
+        let o = Buf::into_owned_string(s);
+        assert_eq!(o.as_ptr(), p);
+    }
+
+    #[test]
+    #[cfg(feature = "smol_str")]
+    fn smol_strings() {
+        let f = fill::<Smol>().unwrap();
+        assert_eq!(f.string, "abcd");
+        assert!(try_fields::<Smol>(&[1, 2], b"ab", &[1, 2]).is_ok());
+
+        // Short strings are held inline
+        let s = Smol::from_owned_string(alloc::string::String::from("abc"));
+        assert!(!s.is_heap_allocated());
+    }
+
+    #[test]
+    #[cfg(feature = "compact_str")]
+    fn compact_strings() {
+        let f = fill::<Compact>().unwrap();
+        assert_eq!(f.string, "abcd");
+        assert!(try_fields::<Compact>(&[1, 2], b"ab", &[1, 2]).is_ok());
+
+        // Short strings are held inline
+        let c = Compact::from_owned_string(alloc::string::String::from("abc"));
+        assert!(!c.is_heap_allocated());
+
+        // Long heap allocated strings move to owned storage without copying
+        let c = Compact::try_string_from("abcdefghijklmnopqrstuvwxyz").unwrap();
+        let p = c.as_ptr();
+        let o = Compact::into_owned_string(c);
+        assert_eq!(o.as_ptr(), p);
+    }
 }
@@ -0,0 +1,236 @@
+//! [`IntoOwned`] and [`FromOwned`] traits support moving [`Stor`] containers to and from [`Owned`] storage
+
+use core::fmt::Debug;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{Owned, Boxed, Ref, Const, Stor};
+
+/// [`IntoOwned`] trait converts containers by value into [`Owned`] containers,
+/// avoiding a copy where the containers are already owned.
+///
+/// This is used by the `into_owned_stor()` method generated by `#[derive(StorConvert)]`.
+pub trait IntoOwned: Stor {
+    /// Convert a list into an owned list
+    fn into_owned_list<T: Debug + Clone>(list: Self::List<T>) -> Vec<T>;
+    /// Convert a string into an owned string
+    fn into_owned_string(string: Self::String) -> String;
+    /// Convert bytes into owned bytes
+    fn into_owned_bytes(bytes: Self::Bytes) -> Vec<u8>;
+}
+
+/// [`FromOwned`] trait converts [`Owned`] containers by value into containers
+/// for this storage type, without copying where this is possible.
+///
+/// This is used by the `from_owned_stor()` method generated by `#[derive(StorConvert)]`.
+pub trait FromOwned: Stor {
+    /// Convert an owned list into a list
+    fn from_owned_list<T: Debug>(list: Vec<T>) -> Self::List<T>;
+    /// Convert an owned string into a string
+    fn from_owned_string(string: String) -> Self::String;
+    /// Convert owned bytes into bytes
+    fn from_owned_bytes(bytes: Vec<u8>) -> Self::Bytes;
+}
+
+impl IntoOwned for Owned {
+    fn into_owned_list<T: Debug + Clone>(list: Self::List<T>) -> Vec<T> {
+        list
+    }
+
+    fn into_owned_string(string: Self::String) -> String {
+        string
+    }
+
+    fn into_owned_bytes(bytes: Self::Bytes) -> Vec<u8> {
+        bytes
+    }
+}
+
+impl FromOwned for Owned {
+    fn from_owned_list<T: Debug>(list: Vec<T>) -> Self::List<T> {
+        list
+    }
+
+    fn from_owned_string(string: String) -> Self::String {
+        string
+    }
+
+    fn from_owned_bytes(bytes: Vec<u8>) -> Self::Bytes {
+        bytes
+    }
+}
+
+impl IntoOwned for Boxed {
+    fn into_owned_list<T: Debug + Clone>(list: Self::List<T>) -> Vec<T> {
+        list.into_vec()
+    }
+
+    fn into_owned_string(string: Self::String) -> String {
+        string.into_string()
+    }
+
+    fn into_owned_bytes(bytes: Self::Bytes) -> Vec<u8> {
+        bytes.into_vec()
+    }
+}
+
+/// Conversion into [`Boxed`] storage shrinks allocations to fit their contents
+impl FromOwned for Boxed {
+    fn from_owned_list<T: Debug>(list: Vec<T>) -> Self::List<T> {
+        list.into_boxed_slice()
+    }
+
+    fn from_owned_string(string: String) -> Self::String {
+        string.into_boxed_str()
+    }
+
+    fn from_owned_bytes(bytes: Vec<u8>) -> Self::Bytes {
+        bytes.into_boxed_slice()
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl IntoOwned for crate::Shared {
+    fn into_owned_list<T: Debug + Clone>(list: Self::List<T>) -> Vec<T> {
+        list.to_vec()
+    }
+
+    fn into_owned_string(string: Self::String) -> String {
+        String::from(&*string)
+    }
+
+    fn into_owned_bytes(bytes: Self::Bytes) -> Vec<u8> {
+        bytes.to_vec()
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl FromOwned for crate::Shared {
+    fn from_owned_list<T: Debug>(list: Vec<T>) -> Self::List<T> {
+        list.into()
+    }
+
+    fn from_owned_string(string: String) -> Self::String {
+        string.into()
+    }
+
+    fn from_owned_bytes(bytes: Vec<u8>) -> Self::Bytes {
+        bytes.into()
+    }
+}
+
+impl IntoOwned for crate::Local {
+    fn into_owned_list<T: Debug + Clone>(list: Self::List<T>) -> Vec<T> {
+        list.to_vec()
+    }
+
+    fn into_owned_string(string: Self::String) -> String {
+        String::from(&*string)
+    }
+
+    fn into_owned_bytes(bytes: Self::Bytes) -> Vec<u8> {
+        bytes.to_vec()
+    }
+}
+
+impl FromOwned for crate::Local {
+    fn from_owned_list<T: Debug>(list: Vec<T>) -> Self::List<T> {
+        list.into()
+    }
+
+    fn from_owned_string(string: String) -> Self::String {
+        string.into()
+    }
+
+    fn from_owned_bytes(bytes: Vec<u8>) -> Self::Bytes {
+        bytes.into()
+    }
+}
+
+impl <'a> IntoOwned for Ref<'a> {
+    fn into_owned_list<T: Debug + Clone>(list: Self::List<T>) -> Vec<T> {
+        list.as_slice().to_vec()
+    }
+
+    fn into_owned_string(string: Self::String) -> String {
+        String::from(string)
+    }
+
+    fn into_owned_bytes(bytes: Self::Bytes) -> Vec<u8> {
+        bytes.to_vec()
+    }
+}
+
+impl <const N: usize> IntoOwned for Const<N> {
+    fn into_owned_list<T: Debug + Clone>(list: Self::List<T>) -> Vec<T> {
+        Vec::from(list)
+    }
+
+    fn into_owned_string(string: Self::String) -> String {
+        String::from(string.as_str())
+    }
+
+    fn into_owned_bytes(bytes: Self::Bytes) -> Vec<u8> {
+        Vec::from(bytes)
+    }
+}
+
+impl <const N: usize> IntoOwned for crate::Bounded<N> {
+    fn into_owned_list<T: Debug + Clone>(list: Self::List<T>) -> Vec<T> {
+        list.to_vec()
+    }
+
+    fn into_owned_string(string: Self::String) -> String {
+        String::from(string.as_str())
+    }
+
+    fn into_owned_bytes(bytes: Self::Bytes) -> Vec<u8> {
+        bytes.to_vec()
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl <const L: usize, const S: usize, const B: usize> IntoOwned for crate::HeaplessSized<L, S, B> {
+    fn into_owned_list<T: Debug + Clone>(list: Self::List<T>) -> Vec<T> {
+        list.into_iter().collect()
+    }
+
+    fn into_owned_string(string: Self::String) -> String {
+        String::from(string.as_str())
+    }
+
+    fn into_owned_bytes(bytes: Self::Bytes) -> Vec<u8> {
+        bytes.into_iter().collect()
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl <const L: usize, const S: usize, const B: usize> IntoOwned for crate::ArrayVecSized<L, S, B> {
+    fn into_owned_list<T: Debug + Clone>(list: Self::List<T>) -> Vec<T> {
+        list.into_iter().collect()
+    }
+
+    fn into_owned_string(string: Self::String) -> String {
+        String::from(string.as_str())
+    }
+
+    fn into_owned_bytes(bytes: Self::Bytes) -> Vec<u8> {
+        bytes.to_vec()
+    }
+}
+
+#[cfg(feature = "tinyvec")]
+impl <const L: usize, const S: usize, const B: usize> IntoOwned for crate::TinyVecSized<L, S, B> {
+    fn into_owned_list<T: Debug + Clone>(list: Self::List<T>) -> Vec<T> {
+        list.to_vec()
+    }
+
+    fn into_owned_string(string: Self::String) -> String {
+        String::from(string.as_str())
+    }
+
+    fn into_owned_bytes(bytes: Self::Bytes) -> Vec<u8> {
+        bytes.to_vec()
+    }
+}
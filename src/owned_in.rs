@@ -0,0 +1,366 @@
+//! [`OwnedIn`] marker for storage using a custom [`Allocator`] (requires nightly and `allocator_api` feature)
+
+use core::alloc::Allocator;
+use core::borrow::Borrow;
+use core::fmt::{Debug, Display, Write};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+use alloc::vec::Vec;
+
+use crate::codec::{CodecError, Decode, Decoder, StorDecode};
+use crate::{CapacityError, FromOwned, IntoOwned, Stor, StorError, StorMut, TryFromStor};
+
+/// OwnedIn marker uses [`Vec`] and [`StringIn`] containers using the allocator `A`
+///
+/// Containers are created with an allocator instance using [`OwnedIn::list_in`],
+/// [`OwnedIn::string_in`] and [`OwnedIn::bytes_in`]. Where `A` implements [`Default`],
+/// as for zero-sized allocators for a fixed memory region, [`StorMut`], [`TryFromStor`]
+/// and [`FromOwned`] are also implemented.
+///
+/// ```
+/// #![feature(allocator_api)]
+/// use std::alloc::Global;
+/// use stor::{Stor, OwnedIn};
+///
+/// struct Header<S: Stor> {
+///     name: S::String,
+///     value: S::Bytes,
+/// }
+///
+/// let h = Header::<OwnedIn<Global>> {
+///     name: OwnedIn::string_in("abc", Global),
+///     value: OwnedIn::bytes_in(&[0xaa], Global),
+/// };
+/// assert_eq!(h.name, "abc");
+/// ```
+pub struct OwnedIn<A> (PhantomData<fn() -> A>);
+
+impl <A: Allocator> Stor for OwnedIn<A> {
+    type List<T: Debug> = Vec<T, A>;
+    type String = StringIn<A>;
+    type Bytes = Vec<u8, A>;
+}
+
+impl <A: Allocator> OwnedIn<A> {
+    /// Copy a list using the provided allocator
+    pub fn list_in<T: Clone>(v: &[T], alloc: A) -> Vec<T, A> {
+        let mut l = Vec::with_capacity_in(v.len(), alloc);
+        l.extend_from_slice(v);
+        l
+    }
+
+    /// Copy a string using the provided allocator
+    pub fn string_in(v: &str, alloc: A) -> StringIn<A> {
+        StringIn::from_str_in(v, alloc)
+    }
+
+    /// Copy bytes using the provided allocator
+    pub fn bytes_in(v: &[u8], alloc: A) -> Vec<u8, A> {
+        Self::list_in(v, alloc)
+    }
+}
+
+// Marker impls are written out to avoid requiring these traits on `A`
+
+impl <A> Debug for OwnedIn<A> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "OwnedIn<{}>", core::any::type_name::<A>())
+    }
+}
+
+impl <A> Clone for OwnedIn<A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl <A> Copy for OwnedIn<A> {}
+
+impl <A> PartialEq for OwnedIn<A> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl <A> Eq for OwnedIn<A> {}
+
+impl <A> PartialOrd for OwnedIn<A> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl <A> Ord for OwnedIn<A> {
+    fn cmp(&self, _other: &Self) -> core::cmp::Ordering {
+        core::cmp::Ordering::Equal
+    }
+}
+
+impl <A> Hash for OwnedIn<A> {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+/// UTF-8 string using the allocator `A`, used by the [`OwnedIn`] marker
+///
+/// This stands in for `alloc::string::String`, which does not yet support custom allocators.
+pub struct StringIn<A: Allocator> {
+    buff: Vec<u8, A>,
+}
+
+impl <A: Allocator> StringIn<A> {
+    /// Create a new empty [`StringIn`] using the provided allocator
+    pub fn new_in(alloc: A) -> Self {
+        Self { buff: Vec::new_in(alloc) }
+    }
+
+    /// Create a [`StringIn`] by copying a string slice using the provided allocator
+    pub fn from_str_in(s: &str, alloc: A) -> Self {
+        let mut v = Self { buff: Vec::with_capacity_in(s.len(), alloc) };
+        v.push_str(s);
+        v
+    }
+
+    /// Create a [`StringIn`] from UTF-8 bytes, returning the bytes if these are not valid
+    pub fn from_utf8(buff: Vec<u8, A>) -> Result<Self, Vec<u8, A>> {
+        match core::str::from_utf8(&buff) {
+            Ok(_) => Ok(Self { buff }),
+            Err(_) => Err(buff),
+        }
+    }
+
+    /// Fetch the string contents
+    pub fn as_str(&self) -> &str {
+        // SAFETY: contents are only ever written from valid `&str`s
+        unsafe { core::str::from_utf8_unchecked(&self.buff) }
+    }
+
+    /// Fetch the string contents as bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buff
+    }
+
+    /// Convert into the underlying byte buffer
+    pub fn into_bytes(self) -> Vec<u8, A> {
+        self.buff
+    }
+
+    /// Fetch the string length in bytes
+    pub fn len(&self) -> usize {
+        self.buff.len()
+    }
+
+    /// Check whether the string is empty
+    pub fn is_empty(&self) -> bool {
+        self.buff.is_empty()
+    }
+
+    /// Append a string slice
+    pub fn push_str(&mut self, s: &str) {
+        self.buff.extend_from_slice(s.as_bytes())
+    }
+
+    /// Append a character
+    pub fn push(&mut self, c: char) {
+        self.push_str(c.encode_utf8(&mut [0u8; 4]))
+    }
+
+    /// Remove all contents from the string
+    pub fn clear(&mut self) {
+        self.buff.clear()
+    }
+
+    /// Fetch the allocator used by this string
+    pub fn allocator(&self) -> &A {
+        self.buff.allocator()
+    }
+}
+
+impl <A: Allocator + Clone> Clone for StringIn<A> {
+    fn clone(&self) -> Self {
+        Self { buff: self.buff.clone() }
+    }
+}
+
+impl <A: Allocator + Default> Default for StringIn<A> {
+    fn default() -> Self {
+        Self::new_in(A::default())
+    }
+}
+
+impl <A: Allocator> Deref for StringIn<A> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl <A: Allocator> AsRef<str> for StringIn<A> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl <A: Allocator> AsRef<[u8]> for StringIn<A> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl <A: Allocator> Borrow<str> for StringIn<A> {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl <A: Allocator> Debug for StringIn<A> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl <A: Allocator> Display for StringIn<A> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+impl <A: Allocator> Write for StringIn<A> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+}
+
+impl <A: Allocator, B: Allocator> PartialEq<StringIn<B>> for StringIn<A> {
+    fn eq(&self, other: &StringIn<B>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl <A: Allocator> Eq for StringIn<A> {}
+
+impl <A: Allocator> PartialEq<str> for StringIn<A> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl <'a, A: Allocator> PartialEq<&'a str> for StringIn<A> {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl <A: Allocator> PartialOrd for StringIn<A> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl <A: Allocator> Ord for StringIn<A> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl <A: Allocator> Hash for StringIn<A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl <A: Allocator> IntoOwned for OwnedIn<A> {
+    fn into_owned_list<T: Debug + Clone>(list: Self::List<T>) -> alloc::vec::Vec<T> {
+        list.into_iter().collect()
+    }
+
+    fn into_owned_string(string: Self::String) -> alloc::string::String {
+        alloc::string::String::from(string.as_str())
+    }
+
+    fn into_owned_bytes(bytes: Self::Bytes) -> alloc::vec::Vec<u8> {
+        bytes.to_vec()
+    }
+}
+
+impl <A: Allocator + Default> FromOwned for OwnedIn<A> {
+    fn from_owned_list<T: Debug>(list: alloc::vec::Vec<T>) -> Self::List<T> {
+        let mut l = Vec::with_capacity_in(list.len(), A::default());
+        l.extend(list);
+        l
+    }
+
+    fn from_owned_string(string: alloc::string::String) -> Self::String {
+        StringIn::from_str_in(&string, A::default())
+    }
+
+    fn from_owned_bytes(bytes: alloc::vec::Vec<u8>) -> Self::Bytes {
+        Self::bytes_in(&bytes, A::default())
+    }
+}
+
+impl <A: Allocator + Default> StorMut for OwnedIn<A> {
+    fn new_list<T: Debug>() -> Self::List<T> {
+        Vec::new_in(A::default())
+    }
+
+    fn new_string() -> Self::String {
+        StringIn::new_in(A::default())
+    }
+
+    fn new_bytes() -> Self::Bytes {
+        Vec::new_in(A::default())
+    }
+
+    fn try_push<T: Debug>(list: &mut Self::List<T>, value: T) -> Result<(), CapacityError> {
+        list.push(value);
+        Ok(())
+    }
+
+    fn extend<T: Debug, I: IntoIterator<Item = T>>(list: &mut Self::List<T>, iter: I) -> Result<(), CapacityError> {
+        list.extend(iter);
+        Ok(())
+    }
+
+    fn clear<T: Debug>(list: &mut Self::List<T>) {
+        list.clear()
+    }
+
+    fn push_str(string: &mut Self::String, value: &str) -> Result<(), CapacityError> {
+        string.push_str(value);
+        Ok(())
+    }
+
+    fn extend_from_slice(bytes: &mut Self::Bytes, value: &[u8]) -> Result<(), CapacityError> {
+        bytes.extend_from_slice(value);
+        Ok(())
+    }
+}
+
+impl <'a, A: Allocator + Default> TryFromStor<'a> for OwnedIn<A> {
+    fn try_list_from<T: Debug + Clone>(v: &'a [T]) -> Result<Self::List<T>, StorError> {
+        Ok(Self::list_in(v, A::default()))
+    }
+
+    fn try_string_from(v: &'a str) -> Result<Self::String, StorError> {
+        Ok(Self::string_in(v, A::default()))
+    }
+
+    fn try_bytes_from(v: &'a [u8]) -> Result<Self::Bytes, StorError> {
+        Ok(Self::bytes_in(v, A::default()))
+    }
+}
+
+impl <'a, A: Allocator + Default> StorDecode<'a> for OwnedIn<A> {
+    fn decode_list<T: Debug + Decode<'a>>(d: &mut Decoder<'a>) -> Result<Self::List<T>, CodecError> {
+        let mut l = Vec::new_in(A::default());
+        d.decode_list(|v| {
+            l.push(v);
+            Ok(())
+        })?;
+        Ok(l)
+    }
+}
@@ -0,0 +1,96 @@
+//! Shared implementations for markers using [`Owned`](crate::Owned) lists and bytes with an alternative string type
+
+/// Implement [`IntoOwned`](crate::IntoOwned), [`FromOwned`](crate::FromOwned), [`StorMut`](crate::StorMut),
+/// [`TryFromStor`](crate::TryFromStor) and [`StorDecode`](crate::codec::StorDecode) for a marker,
+/// delegating lists and bytes to [`Owned`](crate::Owned)
+///
+/// The string type must convert to and from [`String`](alloc::string::String) and from `&str`,
+/// with `push_str` appending a string slice.
+macro_rules! impl_owned_string {
+    ($marker:ty, $string:ty, |$s:ident, $v:ident| $push_str:expr) => {
+        impl $crate::IntoOwned for $marker {
+            fn into_owned_list<T: Debug + Clone>(list: Self::List<T>) -> Vec<T> {
+                list
+            }
+
+            fn into_owned_string(string: Self::String) -> String {
+                String::from(string)
+            }
+
+            fn into_owned_bytes(bytes: Self::Bytes) -> Vec<u8> {
+                bytes
+            }
+        }
+
+        impl $crate::FromOwned for $marker {
+            fn from_owned_list<T: Debug>(list: Vec<T>) -> Self::List<T> {
+                list
+            }
+
+            fn from_owned_string(string: String) -> Self::String {
+                <$string>::from(string)
+            }
+
+            fn from_owned_bytes(bytes: Vec<u8>) -> Self::Bytes {
+                bytes
+            }
+        }
+
+        impl $crate::StorMut for $marker {
+            fn new_list<T: Debug>() -> Self::List<T> {
+                <$crate::Owned as $crate::StorMut>::new_list()
+            }
+
+            fn new_string() -> Self::String {
+                <$string>::default()
+            }
+
+            fn new_bytes() -> Self::Bytes {
+                <$crate::Owned as $crate::StorMut>::new_bytes()
+            }
+
+            fn try_push<T: Debug>(list: &mut Self::List<T>, value: T) -> Result<(), $crate::CapacityError> {
+                <$crate::Owned as $crate::StorMut>::try_push(list, value)
+            }
+
+            fn extend<T: Debug, I: IntoIterator<Item = T>>(list: &mut Self::List<T>, iter: I) -> Result<(), $crate::CapacityError> {
+                <$crate::Owned as $crate::StorMut>::extend(list, iter)
+            }
+
+            fn clear<T: Debug>(list: &mut Self::List<T>) {
+                <$crate::Owned as $crate::StorMut>::clear(list)
+            }
+
+            fn push_str($s: &mut Self::String, $v: &str) -> Result<(), $crate::CapacityError> {
+                $push_str;
+                Ok(())
+            }
+
+            fn extend_from_slice(bytes: &mut Self::Bytes, value: &[u8]) -> Result<(), $crate::CapacityError> {
+                <$crate::Owned as $crate::StorMut>::extend_from_slice(bytes, value)
+            }
+        }
+
+        impl <'a> $crate::TryFromStor<'a> for $marker {
+            fn try_list_from<T: Debug + Clone>(v: &'a [T]) -> Result<Self::List<T>, $crate::StorError> {
+                <$crate::Owned as $crate::TryFromStor<'a>>::try_list_from(v)
+            }
+
+            fn try_string_from(v: &'a str) -> Result<Self::String, $crate::StorError> {
+                Ok(<$string>::from(v))
+            }
+
+            fn try_bytes_from(v: &'a [u8]) -> Result<Self::Bytes, $crate::StorError> {
+                <$crate::Owned as $crate::TryFromStor<'a>>::try_bytes_from(v)
+            }
+        }
+
+        impl <'a> $crate::codec::StorDecode<'a> for $marker {
+            fn decode_list<T: Debug + $crate::codec::Decode<'a>>(d: &mut $crate::codec::Decoder<'a>) -> Result<Self::List<T>, $crate::codec::CodecError> {
+                <$crate::Owned as $crate::codec::StorDecode<'a>>::decode_list(d)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_owned_string;
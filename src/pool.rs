@@ -0,0 +1,491 @@
+//! [`Pooled`] marker for containers allocated from a fixed block [`BlockPool`] (requires `pool` feature)
+
+use core::borrow::{Borrow, BorrowMut};
+use core::cell::UnsafeCell;
+use core::fmt::{Debug, Display, Write};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::{CapacityError, Stor};
+
+/// Pooled marker uses containers each occupying one `B` byte block drawn from a [`BlockPool`],
+/// with blocks returned to the pool when containers are dropped
+///
+/// Pools are sized at compile time and may be `static`, allowing containers to be passed
+/// between tasks or through message queues without copying. Unlike `Heapless`
+/// the size of containers is independent of `B`.
+///
+/// Containers are created with [`BlockPool`] methods, as without a pool
+/// [`TryFromStor`](crate::TryFromStor) and [`StorMut`](crate::StorMut) are not implemented.
+///
+/// With the `serde` feature [`PoolList`] and [`PoolString`] serialise as usual, however
+/// cannot be deserialised without a pool so always return an error at runtime.
+///
+/// ```
+/// use stor::{Stor, Pooled, BlockPool, CapacityError};
+///
+/// #[derive(Debug)]
+/// struct Message<S: Stor> {
+///     topic: S::String,
+///     payload: S::Bytes,
+/// }
+///
+/// static POOL: BlockPool<64, 4> = BlockPool::new();
+///
+/// let m = Message::<Pooled<'static, 64>> {
+///     topic: POOL.string_from("abc")?,
+///     payload: POOL.bytes_from(&[0xaa, 0xbb])?,
+/// };
+/// assert_eq!(POOL.available(), 2);
+///
+/// // Blocks are returned to the pool on drop
+/// drop(m);
+/// assert_eq!(POOL.available(), 4);
+/// # Ok::<(), CapacityError>(())
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Pooled<'a, const B: usize> (PhantomData<&'a ()>);
+
+impl <'a, const B: usize> Stor for Pooled<'a, B> {
+    type List<T: Debug> = PoolList<'a, T, B>;
+    type String = PoolString<'a, B>;
+    type Bytes = PoolList<'a, u8, B>;
+}
+
+#[cfg(feature = "alloc")]
+impl <'a, const B: usize> crate::IntoOwned for Pooled<'a, B> {
+    fn into_owned_list<T: Debug + Clone>(list: Self::List<T>) -> alloc::vec::Vec<T> {
+        list.as_slice().to_vec()
+    }
+
+    fn into_owned_string(string: Self::String) -> alloc::string::String {
+        alloc::string::String::from(string.as_str())
+    }
+
+    fn into_owned_bytes(bytes: Self::Bytes) -> alloc::vec::Vec<u8> {
+        bytes.as_slice().to_vec()
+    }
+}
+
+/// Single block of a [`BlockPool`], aligned to 8 bytes, with items of greater alignment rejected
+#[repr(C, align(8))]
+struct Block<const B: usize> {
+    data: UnsafeCell<MaybeUninit<[u8; B]>>,
+    used: AtomicBool,
+}
+
+// SAFETY: block data is only accessed by the container holding the block
+unsafe impl <const B: usize> Sync for Block<B> {}
+
+/// Pool of `N` blocks of `B` bytes, from which [`Pooled`] containers are allocated
+///
+/// Allocation is lock-free, and fails with [`CapacityError`] where no blocks are available.
+pub struct BlockPool<const B: usize, const N: usize> {
+    blocks: [Block<B>; N],
+}
+
+impl <const B: usize, const N: usize> BlockPool<B, N> {
+    /// Create a new [`BlockPool`], usable in `static` items
+    pub const fn new() -> Self {
+        Self { blocks: [const { Block { data: UnsafeCell::new(MaybeUninit::uninit()), used: AtomicBool::new(false) } }; N] }
+    }
+
+    /// Fetch the number of free blocks
+    pub fn available(&self) -> usize {
+        self.blocks.iter().filter(|b| !b.used.load(Ordering::Relaxed)).count()
+    }
+
+    /// Claim a free block, if any
+    fn claim(&self) -> Result<&Block<B>, CapacityError> {
+        self.blocks.iter()
+            .find(|b| b.used.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed).is_ok())
+            .ok_or(CapacityError)
+    }
+
+    /// Allocate an empty list, failing if `T` is aligned to more than 8 bytes or the pool is exhausted
+    pub fn list<T>(&self) -> Result<PoolList<'_, T, B>, CapacityError> {
+        if core::mem::align_of::<T>() > core::mem::align_of::<Block<B>>() {
+            return Err(CapacityError);
+        }
+
+        Ok(PoolList { block: self.claim()?, len: 0, _type: PhantomData })
+    }
+
+    /// Allocate a list holding clones of the provided items
+    pub fn list_from<T: Clone>(&self, s: &[T]) -> Result<PoolList<'_, T, B>, CapacityError> {
+        let mut l = self.list()?;
+        l.extend_from_slice(s)?;
+        Ok(l)
+    }
+
+    /// Allocate an empty string
+    pub fn string(&self) -> Result<PoolString<'_, B>, CapacityError> {
+        Ok(PoolString { buff: self.list()? })
+    }
+
+    /// Allocate a string holding a copy of the provided string slice
+    pub fn string_from(&self, s: &str) -> Result<PoolString<'_, B>, CapacityError> {
+        Ok(PoolString { buff: self.list_from(s.as_bytes())? })
+    }
+
+    /// Allocate an empty byte buffer
+    pub fn bytes(&self) -> Result<PoolList<'_, u8, B>, CapacityError> {
+        self.list()
+    }
+
+    /// Allocate a byte buffer holding a copy of the provided bytes
+    pub fn bytes_from(&self, b: &[u8]) -> Result<PoolList<'_, u8, B>, CapacityError> {
+        self.list_from(b)
+    }
+}
+
+impl <const B: usize, const N: usize> Default for BlockPool<B, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <const B: usize, const N: usize> Debug for BlockPool<B, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BlockPool").field("block_size", &B).field("blocks", &N).field("available", &self.available()).finish()
+    }
+}
+
+/// List occupying a single block of a [`BlockPool`], used by the [`Pooled`] marker
+pub struct PoolList<'a, T, const B: usize> {
+    block: &'a Block<B>,
+    len: usize,
+    _type: PhantomData<T>,
+}
+
+// SAFETY: a [`PoolList`] exclusively owns its items and block
+unsafe impl <'a, T: Send, const B: usize> Send for PoolList<'a, T, B> {}
+unsafe impl <'a, T: Sync, const B: usize> Sync for PoolList<'a, T, B> {}
+
+impl <'a, T, const B: usize> PoolList<'a, T, B> {
+    /// Fetch a pointer to the block contents
+    fn ptr(&self) -> *mut T {
+        self.block.data.get() as *mut T
+    }
+
+    /// Fetch the list contents as a slice
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: the first `len` items are always initialised
+        unsafe { core::slice::from_raw_parts(self.ptr(), self.len) }
+    }
+
+    /// Fetch the list contents as a mutable slice
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: the first `len` items are always initialised
+        unsafe { core::slice::from_raw_parts_mut(self.ptr(), self.len) }
+    }
+
+    /// Fetch the number of items in the list
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check whether the list is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Check whether the list is full
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    /// Fetch the list capacity, being the number of items fitting in a block
+    pub fn capacity(&self) -> usize {
+        B.checked_div(core::mem::size_of::<T>()).unwrap_or(usize::MAX)
+    }
+
+    /// Append an item, returning it in `Err` if the list is full
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(value);
+        }
+
+        // SAFETY: `len` is within the block capacity
+        unsafe { self.ptr().add(self.len).write(value) };
+        self.len += 1;
+
+        Ok(())
+    }
+
+    /// Remove and return the last item, if any
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        // SAFETY: item was initialised and is no longer tracked by `len`
+        Some(unsafe { self.ptr().add(self.len).read() })
+    }
+
+    /// Append cloned items from a slice, returning [`CapacityError`] and leaving
+    /// the list unchanged if these do not fit
+    pub fn extend_from_slice(&mut self, s: &[T]) -> Result<(), CapacityError> where T: Clone {
+        if s.len() > self.capacity() - self.len {
+            return Err(CapacityError);
+        }
+
+        for v in s {
+            // SAFETY: capacity checked above
+            unsafe { self.ptr().add(self.len).write(v.clone()) };
+            self.len += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Shorten the list to the provided length, dropping any remaining items
+    pub fn truncate(&mut self, len: usize) {
+        while self.len > len {
+            self.len -= 1;
+            // SAFETY: item was initialised and is no longer tracked by `len`
+            unsafe { self.ptr().add(self.len).drop_in_place() };
+        }
+    }
+
+    /// Remove all items from the list
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+}
+
+impl <'a, T, const B: usize> Drop for PoolList<'a, T, B> {
+    fn drop(&mut self) {
+        // SAFETY: the first `len` items are always initialised
+        unsafe { core::ptr::drop_in_place(self.as_mut_slice()) }
+        self.block.used.store(false, Ordering::Release);
+    }
+}
+
+impl <'a, T: Debug, const B: usize> Debug for PoolList<'a, T, B> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl <'a, T, const B: usize> Deref for PoolList<'a, T, B> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl <'a, T, const B: usize> DerefMut for PoolList<'a, T, B> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl <'a, T, const B: usize> AsRef<[T]> for PoolList<'a, T, B> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl <'a, T, const B: usize> AsMut<[T]> for PoolList<'a, T, B> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl <'a, T, const B: usize> Borrow<[T]> for PoolList<'a, T, B> {
+    fn borrow(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl <'a, T, const B: usize> BorrowMut<[T]> for PoolList<'a, T, B> {
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl <'a, 'b, T, const B: usize> IntoIterator for &'b PoolList<'a, T, B> {
+    type Item = &'b T;
+    type IntoIter = core::slice::Iter<'b, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl <'a, 'b, T: PartialEq, const B: usize, const C: usize> PartialEq<PoolList<'b, T, C>> for PoolList<'a, T, B> {
+    fn eq(&self, other: &PoolList<'b, T, C>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl <'a, T: Eq, const B: usize> Eq for PoolList<'a, T, B> {}
+
+impl <'a, T: PartialEq, const B: usize> PartialEq<[T]> for PoolList<'a, T, B> {
+    fn eq(&self, other: &[T]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl <'a, T: PartialEq, const B: usize, const N: usize> PartialEq<[T; N]> for PoolList<'a, T, B> {
+    fn eq(&self, other: &[T; N]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl <'a, T: PartialOrd, const B: usize> PartialOrd for PoolList<'a, T, B> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl <'a, T: Ord, const B: usize> Ord for PoolList<'a, T, B> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl <'a, T: Hash, const B: usize> Hash for PoolList<'a, T, B> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+/// String occupying a single block of a [`BlockPool`], used by the [`Pooled`] marker
+pub struct PoolString<'a, const B: usize> {
+    buff: PoolList<'a, u8, B>,
+}
+
+impl <'a, const B: usize> PoolString<'a, B> {
+    /// Fetch the string contents
+    pub fn as_str(&self) -> &str {
+        // SAFETY: contents are only ever written from valid `&str`s
+        unsafe { core::str::from_utf8_unchecked(self.buff.as_slice()) }
+    }
+
+    /// Fetch the string contents as bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        self.buff.as_slice()
+    }
+
+    /// Fetch the string length in bytes
+    pub fn len(&self) -> usize {
+        self.buff.len()
+    }
+
+    /// Check whether the string is empty
+    pub fn is_empty(&self) -> bool {
+        self.buff.is_empty()
+    }
+
+    /// Fetch the string capacity in bytes
+    pub fn capacity(&self) -> usize {
+        B
+    }
+
+    /// Append a string slice, returning [`CapacityError`] and leaving the string
+    /// unchanged if this does not fit
+    pub fn push_str(&mut self, s: &str) -> Result<(), CapacityError> {
+        self.buff.extend_from_slice(s.as_bytes())
+    }
+
+    /// Append a character, returning [`CapacityError`] if this does not fit
+    pub fn push(&mut self, c: char) -> Result<(), CapacityError> {
+        self.push_str(c.encode_utf8(&mut [0u8; 4]))
+    }
+
+    /// Remove all contents from the string
+    pub fn clear(&mut self) {
+        self.buff.clear()
+    }
+}
+
+impl <'a, const B: usize> Deref for PoolString<'a, B> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl <'a, const B: usize> AsRef<str> for PoolString<'a, B> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl <'a, const B: usize> AsRef<[u8]> for PoolString<'a, B> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl <'a, const B: usize> Borrow<str> for PoolString<'a, B> {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl <'a, const B: usize> Debug for PoolString<'a, B> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl <'a, const B: usize> Display for PoolString<'a, B> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+impl <'a, const B: usize> Write for PoolString<'a, B> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.push_str(s).map_err(|_| core::fmt::Error)
+    }
+}
+
+impl <'a, 'b, const B: usize, const C: usize> PartialEq<PoolString<'b, C>> for PoolString<'a, B> {
+    fn eq(&self, other: &PoolString<'b, C>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl <'a, const B: usize> Eq for PoolString<'a, B> {}
+
+impl <'a, const B: usize> PartialEq<str> for PoolString<'a, B> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl <'a, 'b, const B: usize> PartialEq<&'b str> for PoolString<'a, B> {
+    fn eq(&self, other: &&'b str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl <'a, const B: usize> PartialOrd for PoolString<'a, B> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl <'a, const B: usize> Ord for PoolString<'a, B> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl <'a, const B: usize> Hash for PoolString<'a, B> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
@@ -0,0 +1,164 @@
+//! [`RefList`] borrowed list type used by the [`Ref`](crate::Ref) marker
+
+use core::borrow::Borrow;
+use core::fmt::Debug;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::Deref;
+use core::ptr::NonNull;
+
+/// Borrowed list, equivalent to `&'a [T]`
+///
+/// [`Ref`](crate::Ref) uses this in place of a slice reference as the
+/// generic `List<T>` type cannot require `T: 'a`, which `&'a [T]` would need.
+/// A [`RefList`] can only be created from an `&'a [T]`, and converts back
+/// with [`RefList::as_slice`] or [`RefList::into_slice`].
+pub struct RefList<'a, T> {
+    ptr: NonNull<T>,
+    len: usize,
+    _lifetime: PhantomData<&'a ()>,
+    _type: PhantomData<T>,
+}
+
+// SAFETY: a [`RefList`] is a shared slice reference, so is [`Send`] and [`Sync`] where `&[T]` is
+unsafe impl <'a, T: Sync> Send for RefList<'a, T> {}
+unsafe impl <'a, T: Sync> Sync for RefList<'a, T> {}
+
+impl <'a, T> RefList<'a, T> {
+    /// Create a new [`RefList`] borrowing the provided slice
+    pub const fn new(slice: &'a [T]) -> Self {
+        Self {
+            // SAFETY: slice pointers are never null
+            ptr: unsafe { NonNull::new_unchecked(slice.as_ptr() as *mut T) },
+            len: slice.len(),
+            _lifetime: PhantomData,
+            _type: PhantomData,
+        }
+    }
+
+    /// Fetch the list contents as a slice
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: pointer and length are from a slice borrowed for `'a`,
+        // which `&self` cannot outlive
+        unsafe { core::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Convert back into the underlying `&'a [T]`
+    pub fn into_slice(self) -> &'a [T] where T: 'a {
+        // SAFETY: pointer and length are from a slice borrowed for `'a`
+        unsafe { core::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl <'a, T> Clone for RefList<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl <'a, T> Copy for RefList<'a, T> {}
+
+impl <'a, T> Default for RefList<'a, T> {
+    fn default() -> Self {
+        Self {
+            ptr: NonNull::dangling(),
+            len: 0,
+            _lifetime: PhantomData,
+            _type: PhantomData,
+        }
+    }
+}
+
+impl <'a, T: Debug> Debug for RefList<'a, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl <'a, T> Deref for RefList<'a, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl <'a, T> AsRef<[T]> for RefList<'a, T> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl <'a, T> Borrow<[T]> for RefList<'a, T> {
+    fn borrow(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl <'a, T> From<&'a [T]> for RefList<'a, T> {
+    fn from(slice: &'a [T]) -> Self {
+        Self::new(slice)
+    }
+}
+
+impl <'a, T, const N: usize> From<&'a [T; N]> for RefList<'a, T> {
+    fn from(array: &'a [T; N]) -> Self {
+        Self::new(array)
+    }
+}
+
+impl <'a, 'b, T: PartialEq> PartialEq<RefList<'b, T>> for RefList<'a, T> {
+    fn eq(&self, other: &RefList<'b, T>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl <'a, T: Eq> Eq for RefList<'a, T> {}
+
+impl <'a, T: PartialEq> PartialEq<[T]> for RefList<'a, T> {
+    fn eq(&self, other: &[T]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl <'a, T: PartialEq, const N: usize> PartialEq<[T; N]> for RefList<'a, T> {
+    fn eq(&self, other: &[T; N]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl <'a, T: PartialOrd> PartialOrd for RefList<'a, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl <'a, T: Ord> Ord for RefList<'a, T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl <'a, T: Hash> Hash for RefList<'a, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl <'a, T: 'a> IntoIterator for RefList<'a, T> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_slice().iter()
+    }
+}
+
+impl <'s, 'a, T> IntoIterator for &'s RefList<'a, T> {
+    type Item = &'s T;
+    type IntoIter = core::slice::Iter<'s, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
@@ -0,0 +1,229 @@
+//! [`serde`] support for containers provided by this crate (requires `serde` feature)
+//!
+//! Markers implement [`Serialize`] and [`Deserialize`], so `#[derive(Serialize, Deserialize)]`
+//! on a type generic over `S: Stor` requires no additional bounds. Deserialising with
+//! [`Ref<'de>`](crate::Ref) borrows strings and bytes from the input where the format
+//! supports this. [`RefList`] cannot borrow arbitrary items from the input, so always
+//! returns an error when deserialised (in the same manner as `&str` where the input
+//! cannot be borrowed), allowing types containing lists to derive [`Deserialize`].
+
+use core::fmt::Formatter;
+use core::marker::PhantomData;
+
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{BoundedVec, ConstString, RefList};
+
+impl <'a, T: Serialize> Serialize for RefList<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+/// [`RefList`] cannot be deserialised, this implementation always returns an error
+impl <'a, 'de, T> Deserialize<'de> for RefList<'a, T> {
+    fn deserialize<D: Deserializer<'de>>(_deserializer: D) -> Result<Self, D::Error> {
+        Err(D::Error::custom("borrowed lists cannot be deserialized, use an owned storage type"))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl <'a, T: Serialize> Serialize for crate::CowList<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+/// [`CowList`](crate::CowList) deserialises into an owned list
+#[cfg(feature = "alloc")]
+impl <'a, 'de, T: Deserialize<'de>> Deserialize<'de> for crate::CowList<'a, T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        alloc::vec::Vec::deserialize(deserializer).map(crate::CowList::Owned)
+    }
+}
+
+#[cfg(feature = "bumpalo")]
+impl <'a, T: Serialize> Serialize for crate::BumpList<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+/// [`BumpList`](crate::BumpList) cannot be deserialised without an arena, this implementation always returns an error
+#[cfg(feature = "bumpalo")]
+impl <'a, 'de, T> Deserialize<'de> for crate::BumpList<'a, T> {
+    fn deserialize<D: Deserializer<'de>>(_deserializer: D) -> Result<Self, D::Error> {
+        Err(D::Error::custom("arena lists cannot be deserialized, use an owned storage type"))
+    }
+}
+
+#[cfg(feature = "slab")]
+impl <'a, T: Serialize> Serialize for crate::SlabList<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+/// [`SlabList`](crate::SlabList) cannot be deserialised without a buffer, this implementation always returns an error
+#[cfg(feature = "slab")]
+impl <'a, 'de, T> Deserialize<'de> for crate::SlabList<'a, T> {
+    fn deserialize<D: Deserializer<'de>>(_deserializer: D) -> Result<Self, D::Error> {
+        Err(D::Error::custom("slab lists cannot be deserialized, use an owned storage type"))
+    }
+}
+
+#[cfg(feature = "slab")]
+impl <'a> Serialize for crate::SlabString<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// [`SlabString`](crate::SlabString) cannot be deserialised without a buffer, this implementation always returns an error
+#[cfg(feature = "slab")]
+impl <'a, 'de> Deserialize<'de> for crate::SlabString<'a> {
+    fn deserialize<D: Deserializer<'de>>(_deserializer: D) -> Result<Self, D::Error> {
+        Err(D::Error::custom("slab strings cannot be deserialized, use an owned storage type"))
+    }
+}
+
+#[cfg(all(feature = "pool", target_has_atomic = "8"))]
+impl <'a, T: Serialize, const B: usize> Serialize for crate::PoolList<'a, T, B> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+/// [`PoolList`](crate::PoolList) cannot be deserialised without a pool, this implementation always returns an error
+#[cfg(all(feature = "pool", target_has_atomic = "8"))]
+impl <'a, 'de, T, const B: usize> Deserialize<'de> for crate::PoolList<'a, T, B> {
+    fn deserialize<D: Deserializer<'de>>(_deserializer: D) -> Result<Self, D::Error> {
+        Err(D::Error::custom("pool lists cannot be deserialized, use an owned storage type"))
+    }
+}
+
+#[cfg(all(feature = "pool", target_has_atomic = "8"))]
+impl <'a, const B: usize> Serialize for crate::PoolString<'a, B> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// [`PoolString`](crate::PoolString) cannot be deserialised without a pool, this implementation always returns an error
+#[cfg(all(feature = "pool", target_has_atomic = "8"))]
+impl <'a, 'de, const B: usize> Deserialize<'de> for crate::PoolString<'a, B> {
+    fn deserialize<D: Deserializer<'de>>(_deserializer: D) -> Result<Self, D::Error> {
+        Err(D::Error::custom("pool strings cannot be deserialized, use an owned storage type"))
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl <const N: usize> Serialize for crate::InlineString<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl <'de, const N: usize> Deserialize<'de> for crate::InlineString<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct InlineStringVisitor<const N: usize>;
+
+        impl <'de, const N: usize> Visitor<'de> for InlineStringVisitor<N> {
+            type Value = crate::InlineString<N>;
+
+            fn expecting(&self, f: &mut Formatter) -> core::fmt::Result {
+                write!(f, "a string")
+            }
+
+            fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(crate::InlineString::from(v))
+            }
+
+            fn visit_string<E: Error>(self, v: alloc::string::String) -> Result<Self::Value, E> {
+                Ok(crate::InlineString::from(v))
+            }
+        }
+
+        deserializer.deserialize_str(InlineStringVisitor)
+    }
+}
+
+#[cfg(all(feature = "bytes", target_has_atomic = "ptr"))]
+impl Serialize for crate::BufString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(all(feature = "bytes", target_has_atomic = "ptr"))]
+impl <'de> Deserialize<'de> for crate::BufString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        alloc::string::String::deserialize(deserializer).map(crate::BufString::from)
+    }
+}
+
+impl <const N: usize> Serialize for ConstString<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl <'de, const N: usize> Deserialize<'de> for ConstString<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ConstStringVisitor<const N: usize>;
+
+        impl <'de, const N: usize> Visitor<'de> for ConstStringVisitor<N> {
+            type Value = ConstString<N>;
+
+            fn expecting(&self, f: &mut Formatter) -> core::fmt::Result {
+                write!(f, "a string of at most {} bytes", N)
+            }
+
+            fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+                ConstString::try_from_str(v).map_err(|_| E::invalid_length(v.len(), &self))
+            }
+
+            fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                let s = core::str::from_utf8(v).map_err(|_| E::invalid_value(serde::de::Unexpected::Bytes(v), &self))?;
+                self.visit_str(s)
+            }
+        }
+
+        deserializer.deserialize_str(ConstStringVisitor::<N>)
+    }
+}
+
+impl <T: Serialize, const N: usize> Serialize for BoundedVec<T, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+impl <'de, T: Deserialize<'de>, const N: usize> Deserialize<'de> for BoundedVec<T, N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BoundedVecVisitor<T, const N: usize>(PhantomData<T>);
+
+        impl <'de, T: Deserialize<'de>, const N: usize> Visitor<'de> for BoundedVecVisitor<T, N> {
+            type Value = BoundedVec<T, N>;
+
+            fn expecting(&self, f: &mut Formatter) -> core::fmt::Result {
+                write!(f, "a sequence of at most {} items", N)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut v = BoundedVec::new();
+                while let Some(i) = seq.next_element()? {
+                    if v.push(i).is_err() {
+                        return Err(A::Error::invalid_length(N + 1, &self));
+                    }
+                }
+                Ok(v)
+            }
+        }
+
+        deserializer.deserialize_seq(BoundedVecVisitor::<T, N>(PhantomData))
+    }
+}
+
@@ -0,0 +1,519 @@
+//! [`Slab`] marker for containers allocated from a caller-provided [`SlabBuffer`] (requires `slab` feature)
+
+use core::alloc::Layout;
+use core::borrow::{Borrow, BorrowMut};
+use core::cell::Cell;
+use core::fmt::{Debug, Display, Write};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+
+use crate::{CapacityError, Stor};
+
+/// Slab marker uses fixed capacity containers carved out of one [`SlabBuffer`],
+/// so a whole object shares a single memory budget rather than one per field
+///
+/// Containers are created with [`SlabBuffer`] methods, as without a buffer
+/// [`TryFromStor`](crate::TryFromStor) and [`StorMut`](crate::StorMut) are not implemented.
+///
+/// With the `serde` feature [`SlabList`] and [`SlabString`] serialise as usual, however
+/// cannot be deserialised without a buffer so always return an error at runtime.
+///
+/// ```
+/// use core::mem::MaybeUninit;
+/// use stor::{Stor, Slab, SlabBuffer, CapacityError};
+///
+/// #[derive(Debug)]
+/// struct Header<S: Stor> {
+///     tags: S::List<u16>,
+///     name: S::String,
+///     value: S::Bytes,
+/// }
+///
+/// let mut scratch = [MaybeUninit::<u8>::uninit(); 16];
+/// let slab = SlabBuffer::from_uninit(&mut scratch);
+///
+/// let h = Header::<Slab> {
+///     tags: slab.list_from(&[1, 2])?,
+///     name: slab.string_from("abc")?,
+///     value: slab.bytes_from(&[0xaa, 0xbb])?,
+/// };
+/// assert_eq!(h.name, "abc");
+///
+/// // Allocation fails once the buffer is exhausted
+/// assert_eq!(slab.bytes_from(&[0u8; 16]).unwrap_err(), CapacityError);
+/// # Ok::<(), CapacityError>(())
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Slab<'a> (PhantomData<&'a ()>);
+
+impl <'a> Stor for Slab<'a> {
+    type List<T: Debug> = SlabList<'a, T>;
+    type String = SlabString<'a>;
+    type Bytes = SlabList<'a, u8>;
+}
+
+#[cfg(feature = "alloc")]
+impl <'a> crate::IntoOwned for Slab<'a> {
+    fn into_owned_list<T: Debug + Clone>(list: Self::List<T>) -> alloc::vec::Vec<T> {
+        list.as_slice().to_vec()
+    }
+
+    fn into_owned_string(string: Self::String) -> alloc::string::String {
+        alloc::string::String::from(string.as_str())
+    }
+
+    fn into_owned_bytes(bytes: Self::Bytes) -> alloc::vec::Vec<u8> {
+        bytes.as_slice().to_vec()
+    }
+}
+
+/// Scratch buffer from which [`Slab`] containers are allocated
+///
+/// Each container reserves a fixed capacity from the buffer, which is released
+/// only when the buffer is [`reset`](SlabBuffer::reset) or dropped.
+pub struct SlabBuffer<'b> {
+    ptr: NonNull<u8>,
+    len: usize,
+    used: Cell<usize>,
+    _buff: PhantomData<&'b mut [u8]>,
+}
+
+impl <'b> SlabBuffer<'b> {
+    /// Create a [`SlabBuffer`] using the provided uninitialised buffer,
+    /// with `T` allowing the buffer alignment to be selected
+    ///
+    /// Initialised buffers (such as `&mut [u8]`) are not accepted, as padding within items
+    /// written by containers would leave these partially uninitialised once released.
+    pub fn from_uninit<T>(buff: &'b mut [MaybeUninit<T>]) -> Self {
+        Self {
+            // SAFETY: slice pointers are never null
+            ptr: unsafe { NonNull::new_unchecked(buff.as_mut_ptr() as *mut u8) },
+            len: core::mem::size_of_val(buff),
+            used: Cell::new(0),
+            _buff: PhantomData,
+        }
+    }
+
+    /// Fetch the buffer capacity in bytes
+    pub fn capacity(&self) -> usize {
+        self.len
+    }
+
+    /// Fetch the number of bytes reserved by containers, including alignment padding
+    pub fn used(&self) -> usize {
+        self.used.get()
+    }
+
+    /// Fetch the number of bytes remaining
+    pub fn remaining(&self) -> usize {
+        self.len - self.used.get()
+    }
+
+    /// Release all reservations, requiring that no containers are borrowing the buffer
+    pub fn reset(&mut self) {
+        self.used.set(0)
+    }
+
+    /// Reserve memory for the provided layout
+    fn reserve(&self, layout: Layout) -> Result<NonNull<u8>, CapacityError> {
+        if layout.size() == 0 {
+            // SAFETY: alignment is never zero
+            return Ok(unsafe { NonNull::new_unchecked(layout.align() as *mut u8) });
+        }
+
+        let base = self.ptr.as_ptr() as usize;
+        let start = (base + self.used.get()).checked_next_multiple_of(layout.align()).ok_or(CapacityError)? - base;
+        let end = start.checked_add(layout.size()).ok_or(CapacityError)?;
+        if end > self.len {
+            return Err(CapacityError);
+        }
+
+        self.used.set(end);
+
+        // SAFETY: `start` is within the buffer
+        Ok(unsafe { NonNull::new_unchecked(self.ptr.as_ptr().add(start)) })
+    }
+
+    /// Allocate an empty list with capacity for `capacity` items
+    pub fn list<T>(&self, capacity: usize) -> Result<SlabList<'_, T>, CapacityError> {
+        let layout = Layout::array::<T>(capacity).map_err(|_| CapacityError)?;
+        let ptr = self.reserve(layout)?.cast();
+        Ok(SlabList { ptr, len: 0, capacity, _lifetime: PhantomData, _type: PhantomData })
+    }
+
+    /// Allocate a list holding clones of the provided items
+    pub fn list_from<T: Clone>(&self, s: &[T]) -> Result<SlabList<'_, T>, CapacityError> {
+        let mut l = self.list(s.len())?;
+        l.extend_from_slice(s)?;
+        Ok(l)
+    }
+
+    /// Allocate an empty string with capacity for `capacity` bytes
+    pub fn string(&self, capacity: usize) -> Result<SlabString<'_>, CapacityError> {
+        Ok(SlabString { buff: self.list(capacity)? })
+    }
+
+    /// Allocate a string holding a copy of the provided string slice
+    pub fn string_from(&self, s: &str) -> Result<SlabString<'_>, CapacityError> {
+        Ok(SlabString { buff: self.list_from(s.as_bytes())? })
+    }
+
+    /// Allocate an empty byte buffer with capacity for `capacity` bytes
+    pub fn bytes(&self, capacity: usize) -> Result<SlabList<'_, u8>, CapacityError> {
+        self.list(capacity)
+    }
+
+    /// Allocate a byte buffer holding a copy of the provided bytes
+    pub fn bytes_from(&self, b: &[u8]) -> Result<SlabList<'_, u8>, CapacityError> {
+        self.list_from(b)
+    }
+}
+
+impl <'b> Debug for SlabBuffer<'b> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SlabBuffer").field("capacity", &self.len).field("used", &self.used.get()).finish()
+    }
+}
+
+/// Fixed capacity list allocated from a [`SlabBuffer`], used by the [`Slab`] marker
+pub struct SlabList<'a, T> {
+    ptr: NonNull<T>,
+    len: usize,
+    capacity: usize,
+    _lifetime: PhantomData<&'a ()>,
+    _type: PhantomData<T>,
+}
+
+// SAFETY: a [`SlabList`] exclusively owns its items and region of the buffer
+unsafe impl <'a, T: Send> Send for SlabList<'a, T> {}
+unsafe impl <'a, T: Sync> Sync for SlabList<'a, T> {}
+
+impl <'a, T> SlabList<'a, T> {
+    /// Fetch the list contents as a slice
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: the first `len` items are always initialised
+        unsafe { core::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Fetch the list contents as a mutable slice
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: the first `len` items are always initialised
+        unsafe { core::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Fetch the number of items in the list
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check whether the list is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Check whether the list is full
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity
+    }
+
+    /// Fetch the list capacity
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Append an item, returning it in `Err` if the list is full
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        if self.len == self.capacity {
+            return Err(value);
+        }
+
+        // SAFETY: `len` is within the reserved capacity
+        unsafe { self.ptr.as_ptr().add(self.len).write(value) };
+        self.len += 1;
+
+        Ok(())
+    }
+
+    /// Remove and return the last item, if any
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        // SAFETY: item was initialised and is no longer tracked by `len`
+        Some(unsafe { self.ptr.as_ptr().add(self.len).read() })
+    }
+
+    /// Append cloned items from a slice, returning [`CapacityError`] and leaving
+    /// the list unchanged if these do not fit
+    pub fn extend_from_slice(&mut self, s: &[T]) -> Result<(), CapacityError> where T: Clone {
+        if self.len + s.len() > self.capacity {
+            return Err(CapacityError);
+        }
+
+        for v in s {
+            // SAFETY: capacity checked above
+            unsafe { self.ptr.as_ptr().add(self.len).write(v.clone()) };
+            self.len += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Shorten the list to the provided length, dropping any remaining items
+    pub fn truncate(&mut self, len: usize) {
+        while self.len > len {
+            self.len -= 1;
+            // SAFETY: item was initialised and is no longer tracked by `len`
+            unsafe { self.ptr.as_ptr().add(self.len).drop_in_place() };
+        }
+    }
+
+    /// Remove all items from the list
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+}
+
+impl <'a, T> Drop for SlabList<'a, T> {
+    fn drop(&mut self) {
+        // SAFETY: the first `len` items are always initialised
+        unsafe { core::ptr::drop_in_place(self.as_mut_slice()) }
+    }
+}
+
+/// An empty list with no capacity
+impl <'a, T> Default for SlabList<'a, T> {
+    fn default() -> Self {
+        Self { ptr: NonNull::dangling(), len: 0, capacity: 0, _lifetime: PhantomData, _type: PhantomData }
+    }
+}
+
+impl <'a, T: Debug> Debug for SlabList<'a, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl <'a, T> Deref for SlabList<'a, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl <'a, T> DerefMut for SlabList<'a, T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl <'a, T> AsRef<[T]> for SlabList<'a, T> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl <'a, T> AsMut<[T]> for SlabList<'a, T> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl <'a, T> Borrow<[T]> for SlabList<'a, T> {
+    fn borrow(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl <'a, T> BorrowMut<[T]> for SlabList<'a, T> {
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl <'a, 'b, T> IntoIterator for &'b SlabList<'a, T> {
+    type Item = &'b T;
+    type IntoIter = core::slice::Iter<'b, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl <'a, 'b, T: PartialEq> PartialEq<SlabList<'b, T>> for SlabList<'a, T> {
+    fn eq(&self, other: &SlabList<'b, T>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl <'a, T: Eq> Eq for SlabList<'a, T> {}
+
+impl <'a, T: PartialEq> PartialEq<[T]> for SlabList<'a, T> {
+    fn eq(&self, other: &[T]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl <'a, T: PartialEq, const N: usize> PartialEq<[T; N]> for SlabList<'a, T> {
+    fn eq(&self, other: &[T; N]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl <'a, T: PartialOrd> PartialOrd for SlabList<'a, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl <'a, T: Ord> Ord for SlabList<'a, T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl <'a, T: Hash> Hash for SlabList<'a, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+/// Fixed capacity string allocated from a [`SlabBuffer`], used by the [`Slab`] marker
+#[derive(Default)]
+pub struct SlabString<'a> {
+    buff: SlabList<'a, u8>,
+}
+
+impl <'a> SlabString<'a> {
+    /// Fetch the string contents
+    pub fn as_str(&self) -> &str {
+        // SAFETY: contents are only ever written from valid `&str`s
+        unsafe { core::str::from_utf8_unchecked(self.buff.as_slice()) }
+    }
+
+    /// Fetch the string contents as bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        self.buff.as_slice()
+    }
+
+    /// Fetch the string length in bytes
+    pub fn len(&self) -> usize {
+        self.buff.len()
+    }
+
+    /// Check whether the string is empty
+    pub fn is_empty(&self) -> bool {
+        self.buff.is_empty()
+    }
+
+    /// Fetch the string capacity in bytes
+    pub fn capacity(&self) -> usize {
+        self.buff.capacity()
+    }
+
+    /// Append a string slice, returning [`CapacityError`] and leaving the string
+    /// unchanged if this does not fit
+    pub fn push_str(&mut self, s: &str) -> Result<(), CapacityError> {
+        self.buff.extend_from_slice(s.as_bytes())
+    }
+
+    /// Append a character, returning [`CapacityError`] if this does not fit
+    pub fn push(&mut self, c: char) -> Result<(), CapacityError> {
+        self.push_str(c.encode_utf8(&mut [0u8; 4]))
+    }
+
+    /// Remove all contents from the string
+    pub fn clear(&mut self) {
+        self.buff.clear()
+    }
+}
+
+impl <'a> Deref for SlabString<'a> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl <'a> AsRef<str> for SlabString<'a> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl <'a> AsRef<[u8]> for SlabString<'a> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl <'a> Borrow<str> for SlabString<'a> {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl <'a> Debug for SlabString<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl <'a> Display for SlabString<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+impl <'a> Write for SlabString<'a> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.push_str(s).map_err(|_| core::fmt::Error)
+    }
+}
+
+impl <'a, 'b> PartialEq<SlabString<'b>> for SlabString<'a> {
+    fn eq(&self, other: &SlabString<'b>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl <'a> Eq for SlabString<'a> {}
+
+impl <'a> PartialEq<str> for SlabString<'a> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl <'a, 'b> PartialEq<&'b str> for SlabString<'a> {
+    fn eq(&self, other: &&'b str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl <'a> PartialOrd for SlabString<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl <'a> Ord for SlabString<'a> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl <'a> Hash for SlabString<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
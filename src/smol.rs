@@ -0,0 +1,45 @@
+//! [`Smol`] marker for owned storage with [`SmolStr`] strings (requires `smol_str` feature)
+
+use core::fmt::Debug;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use smol_str::SmolStr;
+
+use crate::owned_string::impl_owned_string;
+use crate::Stor;
+
+/// Smol marker uses [`Vec`] for lists and bytes, with [`SmolStr`] strings that hold short
+/// contents inline and share longer contents for cheap cloning
+///
+/// [`SmolStr`] is immutable, so [`StorMut::push_str`](crate::StorMut::push_str) rebuilds the string.
+///
+/// ```
+/// use stor::{Stor, Smol, TryFromStor};
+///
+/// #[derive(Debug)]
+/// struct Entry<S: Stor> {
+///     name: S::String,
+///     value: S::Bytes,
+/// }
+///
+/// let e = Entry::<Smol> {
+///     name: Smol::try_string_from("abc").unwrap(),
+///     value: vec![0xaa, 0xbb],
+/// };
+/// assert_eq!(e.name, "abc");
+/// assert!(!e.name.is_heap_allocated());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Smol;
+
+impl Stor for Smol {
+    type List<T: Debug> = Vec<T>;
+    type String = SmolStr;
+    type Bytes = Vec<u8>;
+}
+
+impl_owned_string!(Smol, SmolStr, |string, value| *string = [string.as_str(), value].into_iter().collect());
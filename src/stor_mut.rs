@@ -0,0 +1,223 @@
+//! [`StorMut`] trait supports building and growing [`Stor`] containers generically
+
+use core::fmt::Debug;
+
+use crate::{CapacityError, Stor};
+
+/// [`StorMut`] trait provides construction and mutation of [`Stor`] containers,
+/// allowing the same code to fill heap-backed and fixed-capacity values.
+///
+/// ```
+/// use stor::{Stor, StorMut, CapacityError};
+///
+/// fn parse<S: StorMut>(data: &[u8]) -> Result<S::List<u8>, CapacityError> {
+///     let mut list = S::new_list();
+///     S::extend(&mut list, data.iter().copied().filter(|v| *v != 0))?;
+///     Ok(list)
+/// }
+///
+/// assert_eq!(parse::<stor::Owned>(&[1, 0, 2]).unwrap(), vec![1, 2]);
+/// assert_eq!(parse::<stor::Heapless<1>>(&[1, 0, 2]), Err(CapacityError));
+/// ```
+pub trait StorMut: Stor {
+    /// Create a new empty list
+    fn new_list<T: Debug>() -> Self::List<T>;
+    /// Create a new empty string
+    fn new_string() -> Self::String;
+    /// Create a new empty byte buffer
+    fn new_bytes() -> Self::Bytes;
+
+    /// Append an item to a list, returning [`CapacityError`] if the list is full
+    fn try_push<T: Debug>(list: &mut Self::List<T>, value: T) -> Result<(), CapacityError>;
+
+    /// Append an item to a list
+    ///
+    /// # Panics
+    /// If the list does not have capacity for the item
+    fn push<T: Debug>(list: &mut Self::List<T>, value: T) {
+        Self::try_push(list, value).expect("list capacity exceeded")
+    }
+
+    /// Append items to a list, stopping with [`CapacityError`] at the first item that does not fit
+    fn extend<T: Debug, I: IntoIterator<Item = T>>(list: &mut Self::List<T>, iter: I) -> Result<(), CapacityError> {
+        for v in iter {
+            Self::try_push(list, v)?;
+        }
+        Ok(())
+    }
+
+    /// Remove all items from a list
+    fn clear<T: Debug>(list: &mut Self::List<T>);
+
+    /// Append a string slice to a string, returning [`CapacityError`] if it does not fit
+    fn push_str(string: &mut Self::String, value: &str) -> Result<(), CapacityError>;
+
+    /// Append a byte slice to a byte buffer, returning [`CapacityError`] if it does not fit
+    fn extend_from_slice(bytes: &mut Self::Bytes, value: &[u8]) -> Result<(), CapacityError>;
+}
+
+#[cfg(feature = "alloc")]
+impl StorMut for crate::Owned {
+    fn new_list<T: Debug>() -> Self::List<T> {
+        alloc::vec::Vec::new()
+    }
+
+    fn new_string() -> Self::String {
+        alloc::string::String::new()
+    }
+
+    fn new_bytes() -> Self::Bytes {
+        alloc::vec::Vec::new()
+    }
+
+    fn try_push<T: Debug>(list: &mut Self::List<T>, value: T) -> Result<(), CapacityError> {
+        list.push(value);
+        Ok(())
+    }
+
+    fn extend<T: Debug, I: IntoIterator<Item = T>>(list: &mut Self::List<T>, iter: I) -> Result<(), CapacityError> {
+        list.extend(iter);
+        Ok(())
+    }
+
+    fn clear<T: Debug>(list: &mut Self::List<T>) {
+        list.clear()
+    }
+
+    fn push_str(string: &mut Self::String, value: &str) -> Result<(), CapacityError> {
+        string.push_str(value);
+        Ok(())
+    }
+
+    fn extend_from_slice(bytes: &mut Self::Bytes, value: &[u8]) -> Result<(), CapacityError> {
+        bytes.extend_from_slice(value);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl <const L: usize, const S: usize, const B: usize> StorMut for crate::HeaplessSized<L, S, B> {
+    fn new_list<T: Debug>() -> Self::List<T> {
+        heapless::Vec::new()
+    }
+
+    fn new_string() -> Self::String {
+        heapless::String::new()
+    }
+
+    fn new_bytes() -> Self::Bytes {
+        heapless::Vec::new()
+    }
+
+    fn try_push<T: Debug>(list: &mut Self::List<T>, value: T) -> Result<(), CapacityError> {
+        list.push(value).map_err(|_| CapacityError)
+    }
+
+    fn clear<T: Debug>(list: &mut Self::List<T>) {
+        list.clear()
+    }
+
+    fn push_str(string: &mut Self::String, value: &str) -> Result<(), CapacityError> {
+        string.push_str(value).map_err(|_| CapacityError)
+    }
+
+    fn extend_from_slice(bytes: &mut Self::Bytes, value: &[u8]) -> Result<(), CapacityError> {
+        bytes.extend_from_slice(value).map_err(|_| CapacityError)
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl <const L: usize, const S: usize, const B: usize> StorMut for crate::ArrayVecSized<L, S, B> {
+    fn new_list<T: Debug>() -> Self::List<T> {
+        arrayvec::ArrayVec::new()
+    }
+
+    fn new_string() -> Self::String {
+        arrayvec::ArrayString::new()
+    }
+
+    fn new_bytes() -> Self::Bytes {
+        arrayvec::ArrayVec::new()
+    }
+
+    fn try_push<T: Debug>(list: &mut Self::List<T>, value: T) -> Result<(), CapacityError> {
+        list.try_push(value).map_err(|_| CapacityError)
+    }
+
+    fn clear<T: Debug>(list: &mut Self::List<T>) {
+        list.clear()
+    }
+
+    fn push_str(string: &mut Self::String, value: &str) -> Result<(), CapacityError> {
+        string.try_push_str(value).map_err(|_| CapacityError)
+    }
+
+    fn extend_from_slice(bytes: &mut Self::Bytes, value: &[u8]) -> Result<(), CapacityError> {
+        bytes.try_extend_from_slice(value).map_err(|_| CapacityError)
+    }
+}
+
+#[cfg(feature = "tinyvec")]
+impl <const L: usize, const S: usize, const B: usize> StorMut for crate::TinyVecSized<L, S, B> {
+    fn new_list<T: Debug>() -> Self::List<T> {
+        crate::BoundedVec::new()
+    }
+
+    fn new_string() -> Self::String {
+        crate::ConstString::new()
+    }
+
+    fn new_bytes() -> Self::Bytes {
+        tinyvec::ArrayVec::new()
+    }
+
+    fn try_push<T: Debug>(list: &mut Self::List<T>, value: T) -> Result<(), CapacityError> {
+        list.push(value).map_err(|_| CapacityError)
+    }
+
+    fn clear<T: Debug>(list: &mut Self::List<T>) {
+        list.clear()
+    }
+
+    fn push_str(string: &mut Self::String, value: &str) -> Result<(), CapacityError> {
+        string.push_str(value)
+    }
+
+    fn extend_from_slice(bytes: &mut Self::Bytes, value: &[u8]) -> Result<(), CapacityError> {
+        if value.len() > B - bytes.len() {
+            return Err(CapacityError);
+        }
+        bytes.extend_from_slice(value);
+        Ok(())
+    }
+}
+
+impl <const N: usize> StorMut for crate::Bounded<N> {
+    fn new_list<T: Debug>() -> Self::List<T> {
+        crate::BoundedVec::new()
+    }
+
+    fn new_string() -> Self::String {
+        crate::ConstString::new()
+    }
+
+    fn new_bytes() -> Self::Bytes {
+        crate::BoundedVec::new()
+    }
+
+    fn try_push<T: Debug>(list: &mut Self::List<T>, value: T) -> Result<(), CapacityError> {
+        list.push(value).map_err(|_| CapacityError)
+    }
+
+    fn clear<T: Debug>(list: &mut Self::List<T>) {
+        list.clear()
+    }
+
+    fn push_str(string: &mut Self::String, value: &str) -> Result<(), CapacityError> {
+        string.push_str(value)
+    }
+
+    fn extend_from_slice(bytes: &mut Self::Bytes, value: &[u8]) -> Result<(), CapacityError> {
+        bytes.extend_from_slice(value)
+    }
+}
@@ -0,0 +1,223 @@
+//! [`TryFromStor`] trait supports fallible construction of [`Stor`] containers
+
+use core::fmt::Debug;
+
+use crate::{Bounded, BoundedVec, Const, ConstString, Ref, RefList, Stor, StorError};
+
+/// [`TryFromStor`] trait constructs [`Stor`] containers from borrowed data,
+/// allowing generic code to build fields without knowing the storage backend.
+///
+/// The lifetime `'a` is that of the source data, allowing [`Ref`] to borrow
+/// rather than copy. Storage that copies data implements this for all `'a`.
+///
+/// Markers allocating containers from a caller-provided arena, buffer or pool cannot
+/// construct containers from data alone, so do not implement this trait and instead
+/// provide constructors taking the allocation context.
+///
+/// ```
+/// use stor::{Stor, TryFromStor, StorError};
+///
+/// #[derive(Debug)]
+/// struct Header<S: Stor> {
+///     name: S::String,
+///     value: S::Bytes,
+/// }
+///
+/// fn parse<'a, S: TryFromStor<'a>>(name: &'a [u8], value: &'a [u8]) -> Result<Header<S>, StorError> {
+///     Ok(Header {
+///         name: S::try_string_from_utf8(name)?,
+///         value: S::try_bytes_from(value)?,
+///     })
+/// }
+///
+/// let h = parse::<stor::Ref>(b"abc", &[0xaa]).unwrap();
+/// assert_eq!(h.name, "abc");
+///
+/// let e = parse::<stor::Const<2>>(b"abc", &[0xaa]).unwrap_err();
+/// assert_eq!(e, StorError::CapacityOverflow);
+/// ```
+pub trait TryFromStor<'a>: Stor {
+    /// Create a list from a slice
+    fn try_list_from<T: Debug + Clone>(v: &'a [T]) -> Result<Self::List<T>, StorError>;
+
+    /// Create a string from a string slice
+    fn try_string_from(v: &'a str) -> Result<Self::String, StorError>;
+
+    /// Create bytes from a byte slice
+    fn try_bytes_from(v: &'a [u8]) -> Result<Self::Bytes, StorError>;
+
+    /// Create a string from a byte slice, returning [`StorError::InvalidUtf8`] where this is not valid UTF-8
+    fn try_string_from_utf8(v: &'a [u8]) -> Result<Self::String, StorError> {
+        let s = core::str::from_utf8(v)?;
+        Self::try_string_from(s)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl <'a> TryFromStor<'a> for crate::Owned {
+    fn try_list_from<T: Debug + Clone>(v: &'a [T]) -> Result<Self::List<T>, StorError> {
+        Ok(v.to_vec())
+    }
+
+    fn try_string_from(v: &'a str) -> Result<Self::String, StorError> {
+        Ok(alloc::string::String::from(v))
+    }
+
+    fn try_bytes_from(v: &'a [u8]) -> Result<Self::Bytes, StorError> {
+        Ok(v.to_vec())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl <'a> TryFromStor<'a> for crate::Boxed {
+    fn try_list_from<T: Debug + Clone>(v: &'a [T]) -> Result<Self::List<T>, StorError> {
+        Ok(v.into())
+    }
+
+    fn try_string_from(v: &'a str) -> Result<Self::String, StorError> {
+        Ok(v.into())
+    }
+
+    fn try_bytes_from(v: &'a [u8]) -> Result<Self::Bytes, StorError> {
+        Ok(v.into())
+    }
+}
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl <'a> TryFromStor<'a> for crate::Shared {
+    fn try_list_from<T: Debug + Clone>(v: &'a [T]) -> Result<Self::List<T>, StorError> {
+        Ok(v.into())
+    }
+
+    fn try_string_from(v: &'a str) -> Result<Self::String, StorError> {
+        Ok(v.into())
+    }
+
+    fn try_bytes_from(v: &'a [u8]) -> Result<Self::Bytes, StorError> {
+        Ok(v.into())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl <'a> TryFromStor<'a> for crate::Local {
+    fn try_list_from<T: Debug + Clone>(v: &'a [T]) -> Result<Self::List<T>, StorError> {
+        Ok(v.into())
+    }
+
+    fn try_string_from(v: &'a str) -> Result<Self::String, StorError> {
+        Ok(v.into())
+    }
+
+    fn try_bytes_from(v: &'a [u8]) -> Result<Self::Bytes, StorError> {
+        Ok(v.into())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl <'a> TryFromStor<'a> for crate::Cow<'a> {
+    fn try_list_from<T: Debug + Clone>(v: &'a [T]) -> Result<Self::List<T>, StorError> {
+        Ok(v.into())
+    }
+
+    fn try_string_from(v: &'a str) -> Result<Self::String, StorError> {
+        Ok(v.into())
+    }
+
+    fn try_bytes_from(v: &'a [u8]) -> Result<Self::Bytes, StorError> {
+        Ok(v.into())
+    }
+}
+
+impl <'a> TryFromStor<'a> for Ref<'a> {
+    fn try_list_from<T: Debug + Clone>(v: &'a [T]) -> Result<Self::List<T>, StorError> {
+        Ok(RefList::new(v))
+    }
+
+    fn try_string_from(v: &'a str) -> Result<Self::String, StorError> {
+        Ok(v)
+    }
+
+    fn try_bytes_from(v: &'a [u8]) -> Result<Self::Bytes, StorError> {
+        Ok(v)
+    }
+}
+
+impl <'a, const N: usize> TryFromStor<'a> for Const<N> {
+    fn try_list_from<T: Debug + Clone>(v: &'a [T]) -> Result<Self::List<T>, StorError> {
+        if v.len() != N {
+            return Err(StorError::LengthMismatch { expected: N, actual: v.len() });
+        }
+        Ok(core::array::from_fn(|i| v[i].clone()))
+    }
+
+    fn try_string_from(v: &'a str) -> Result<Self::String, StorError> {
+        Ok(ConstString::try_from_str(v)?)
+    }
+
+    fn try_bytes_from(v: &'a [u8]) -> Result<Self::Bytes, StorError> {
+        v.try_into().map_err(|_| StorError::LengthMismatch { expected: N, actual: v.len() })
+    }
+}
+
+impl <'a, const N: usize> TryFromStor<'a> for Bounded<N> {
+    fn try_list_from<T: Debug + Clone>(v: &'a [T]) -> Result<Self::List<T>, StorError> {
+        Ok(BoundedVec::try_from(v)?)
+    }
+
+    fn try_string_from(v: &'a str) -> Result<Self::String, StorError> {
+        Ok(ConstString::try_from_str(v)?)
+    }
+
+    fn try_bytes_from(v: &'a [u8]) -> Result<Self::Bytes, StorError> {
+        Ok(BoundedVec::try_from(v)?)
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl <'a, const L: usize, const S: usize, const B: usize> TryFromStor<'a> for crate::HeaplessSized<L, S, B> {
+    fn try_list_from<T: Debug + Clone>(v: &'a [T]) -> Result<Self::List<T>, StorError> {
+        heapless::Vec::from_slice(v).map_err(|_| StorError::CapacityOverflow)
+    }
+
+    fn try_string_from(v: &'a str) -> Result<Self::String, StorError> {
+        let mut s = heapless::String::new();
+        s.push_str(v).map_err(|_| StorError::CapacityOverflow)?;
+        Ok(s)
+    }
+
+    fn try_bytes_from(v: &'a [u8]) -> Result<Self::Bytes, StorError> {
+        heapless::Vec::from_slice(v).map_err(|_| StorError::CapacityOverflow)
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl <'a, const L: usize, const S: usize, const B: usize> TryFromStor<'a> for crate::ArrayVecSized<L, S, B> {
+    fn try_list_from<T: Debug + Clone>(v: &'a [T]) -> Result<Self::List<T>, StorError> {
+        arrayvec::ArrayVec::try_from(v).map_err(|_| StorError::CapacityOverflow)
+    }
+
+    fn try_string_from(v: &'a str) -> Result<Self::String, StorError> {
+        arrayvec::ArrayString::from(v).map_err(|_| StorError::CapacityOverflow)
+    }
+
+    fn try_bytes_from(v: &'a [u8]) -> Result<Self::Bytes, StorError> {
+        arrayvec::ArrayVec::try_from(v).map_err(|_| StorError::CapacityOverflow)
+    }
+}
+
+#[cfg(feature = "tinyvec")]
+impl <'a, const L: usize, const S: usize, const B: usize> TryFromStor<'a> for crate::TinyVecSized<L, S, B> {
+    fn try_list_from<T: Debug + Clone>(v: &'a [T]) -> Result<Self::List<T>, StorError> {
+        Ok(BoundedVec::try_from(v)?)
+    }
+
+    fn try_string_from(v: &'a str) -> Result<Self::String, StorError> {
+        Ok(ConstString::try_from_str(v)?)
+    }
+
+    fn try_bytes_from(v: &'a [u8]) -> Result<Self::Bytes, StorError> {
+        let mut b = tinyvec::ArrayVec::new();
+        <Self as crate::StorMut>::extend_from_slice(&mut b, v)?;
+        Ok(b)
+    }
+}
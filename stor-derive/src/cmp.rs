@@ -0,0 +1,84 @@
+//! `StorPartialEq` derive implementation, comparing contents across storage types
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Ident};
+
+use crate::{bindings, classify, find_stor_param, pattern, replace, variants, Kind};
+
+pub fn stor_partial_eq(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let stor = find_stor_param(&input).ok_or_else(|| {
+        syn::Error::new(input.ident.span(), "StorPartialEq requires a type parameter bounded by `Stor`")
+    })?;
+    let other = Ident::new("__S2", Span::call_site());
+
+    let name = &input.ident;
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    let other_ty = replace(quote!(#name #ty_generics), &stor, &other);
+
+    // The other storage parameter takes the same bounds as the original
+    let stor_bounds = input.generics.type_params().find(|p| p.ident == stor).map(|p| {
+        let b = p.bounds.iter();
+        quote!(#(#b +)*)
+    });
+
+    let mut generics = input.generics.clone();
+    generics.params.push(syn::TypeParam::from(other.clone()).into());
+    let (impl_generics, _, _) = generics.split_for_impl();
+
+    // Existing predicates apply to both instantiations
+    let mut bounds = vec![];
+    if let Some(w) = where_clause {
+        for p in &w.predicates {
+            bounds.push(quote!(#p));
+            bounds.push(replace(quote!(#p), &stor, &other));
+        }
+    }
+
+    let variants = variants(&input, "StorPartialEq")?;
+
+    let mut arms = vec![];
+    for (path, fields) in &variants {
+        let a = bindings(fields);
+        let b: Vec<_> = (0..fields.len()).map(|i| format_ident!("__o{}", i)).collect();
+        let (pa, pb) = (pattern(fields, &a), pattern(fields, &b));
+
+        let mut cmp = vec![];
+        for ((f, a), b) in fields.iter().zip(&a).zip(&b) {
+            let ty = &f.ty;
+            cmp.push(match classify(ty, &stor) {
+                Kind::String => quote!(::stor::cmp::eq_string(#a, #b)),
+                Kind::Bytes => quote!(::stor::cmp::eq_bytes(#a, #b)),
+                Kind::List(t, _) => {
+                    let u = replace(quote!(#t), &stor, &other);
+                    bounds.push(quote!(#t: ::core::cmp::PartialEq<#u>));
+                    quote!(::stor::cmp::eq_list::<#t, #u, _, _>(#a, #b))
+                }
+                Kind::Phantom => quote!(true),
+                Kind::Nested | Kind::Plain => {
+                    let u = replace(quote!(#ty), &stor, &other);
+                    bounds.push(quote!(#ty: ::core::cmp::PartialEq<#u>));
+                    quote!(#a == #b)
+                }
+            });
+        }
+
+        arms.push(quote!((#path #pa, #path #pb) => true #(&& #cmp)*));
+    }
+
+    let body = match &input.data {
+        Data::Enum(_) if variants.is_empty() => quote!(match *self {}),
+        Data::Enum(_) if variants.len() > 1 => quote!(match (self, other) { #(#arms,)* _ => false, }),
+        _ => quote!(match (self, other) { #(#arms,)* }),
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::core::cmp::PartialEq<#other_ty> for #name #ty_generics
+        where #other: #stor_bounds, #(#bounds,)*
+        {
+            fn eq(&self, other: &#other_ty) -> bool {
+                #body
+            }
+        }
+    })
+}
@@ -0,0 +1,142 @@
+//! `Encode` and `Decode` derive implementations for the `stor::codec` module
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{DeriveInput, Fields, Ident, Lifetime, LifetimeParam};
+
+use crate::{bindings, classify, find_stor_param, pattern, variants, wrap, Kind};
+
+/// Classify a field, treating all fields as plain where there is no storage parameter
+fn kind<'a>(ty: &'a syn::Type, stor: Option<&Ident>) -> Kind<'a> {
+    match stor {
+        Some(s) => classify(ty, s),
+        None => Kind::Plain,
+    }
+}
+
+pub fn encode(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let stor = find_stor_param(&input);
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut bounds = vec![];
+    let mut arms = vec![];
+
+    for (index, (path, fields)) in variants(&input, "Encode")?.into_iter().enumerate() {
+        let bindings = bindings(fields);
+        let pattern = pattern(fields, &bindings);
+
+        let mut body = vec![];
+
+        // Enum variants are prefixed by their index
+        if let syn::Data::Enum(_) = &input.data {
+            body.push(quote!(__e.encode_len(#index)?;));
+        }
+
+        for (f, b) in fields.iter().zip(&bindings) {
+            let ty = &f.ty;
+            body.push(match kind(ty, stor.as_ref()) {
+                Kind::String => quote!(__e.encode_str(::core::convert::AsRef::<str>::as_ref(#b))?;),
+                Kind::Bytes => quote!(__e.encode_bytes(::core::convert::AsRef::<[u8]>::as_ref(#b))?;),
+                Kind::List(t, _) => {
+                    bounds.push(quote!(#t: ::stor::codec::Encode));
+                    quote!(__e.encode_list(::core::convert::AsRef::<[#t]>::as_ref(#b))?;)
+                }
+                Kind::Phantom => quote!(let _ = #b;),
+                Kind::Nested | Kind::Plain => {
+                    bounds.push(quote!(#ty: ::stor::codec::Encode));
+                    quote!(::stor::codec::Encode::encode(#b, __e)?;)
+                }
+            });
+        }
+
+        arms.push(quote!(#path #pattern => { #(#body)* }));
+    }
+
+    let predicates = where_clause.map(|w| w.predicates.iter().collect::<Vec<_>>()).unwrap_or_default();
+
+    Ok(quote! {
+        impl #impl_generics ::stor::codec::Encode for #name #ty_generics where #(#predicates,)* #(#bounds,)* {
+            fn encode(&self, __e: &mut ::stor::codec::Encoder) -> ::core::result::Result<(), ::stor::codec::CodecError> {
+                match self { #(#arms,)* }
+                ::core::result::Result::Ok(())
+            }
+        }
+    })
+}
+
+pub fn decode(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let stor = find_stor_param(&input);
+
+    let name = &input.ident;
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // Add a lifetime for the decoded buffer
+    let lifetime = Lifetime::new("'__de", Span::call_site());
+    let mut generics = input.generics.clone();
+    generics.params.insert(0, LifetimeParam::new(lifetime.clone()).into());
+    let (impl_generics, _, _) = generics.split_for_impl();
+
+    let mut bounds = vec![];
+    if let Some(s) = &stor {
+        bounds.push(quote!(#s: ::stor::codec::StorDecode<#lifetime>));
+    }
+
+    let mut ctors = vec![];
+    for (path, fields) in variants(&input, "Decode")? {
+        let mut values = vec![];
+
+        for f in fields.iter() {
+            let ty = &f.ty;
+            let v = match kind(ty, stor.as_ref()) {
+                Kind::String => quote!(<#stor as ::stor::codec::StorDecode<#lifetime>>::decode_string(__d)?),
+                Kind::Bytes => quote!(<#stor as ::stor::codec::StorDecode<#lifetime>>::decode_bytes(__d)?),
+                Kind::List(t, _) => {
+                    bounds.push(quote!(#t: ::stor::codec::Decode<#lifetime>));
+                    quote!(<#stor as ::stor::codec::StorDecode<#lifetime>>::decode_list::<#t>(__d)?)
+                }
+                Kind::Phantom => quote!(::core::marker::PhantomData),
+                Kind::Nested | Kind::Plain => {
+                    bounds.push(quote!(#ty: ::stor::codec::Decode<#lifetime>));
+                    quote!(<#ty as ::stor::codec::Decode<#lifetime>>::decode(__d)?)
+                }
+            };
+
+            values.push(match &f.ident {
+                Some(n) => quote!(#n: #v),
+                None => v,
+            });
+        }
+
+        let values = match fields {
+            Fields::Unit => quote!(),
+            _ => wrap(fields, values),
+        };
+        ctors.push(quote!(#path #values));
+    }
+
+    // Enums are prefixed by the variant index
+    let body = match &input.data {
+        syn::Data::Enum(_) => {
+            let index = 0..ctors.len();
+            quote! {
+                match __d.decode_len()? {
+                    #(#index => ::core::result::Result::Ok(#ctors),)*
+                    _ => ::core::result::Result::Err(::stor::codec::CodecError::InvalidValue),
+                }
+            }
+        }
+        _ => quote!(::core::result::Result::Ok(#(#ctors)*)),
+    };
+
+    let predicates = where_clause.map(|w| w.predicates.iter().collect::<Vec<_>>()).unwrap_or_default();
+
+    Ok(quote! {
+        impl #impl_generics ::stor::codec::Decode<#lifetime> for #name #ty_generics where #(#predicates,)* #(#bounds,)* {
+            fn decode(__d: &mut ::stor::codec::Decoder<#lifetime>) -> ::core::result::Result<Self, ::stor::codec::CodecError> {
+                #body
+            }
+        }
+    })
+}
@@ -0,0 +1,522 @@
+//! Derive macros for the [`stor`](https://docs.rs/stor) crate
+//!
+//! These are re-exported by `stor` with the `derive` feature, and should be used from there.
+
+mod cmp;
+mod codec;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, spanned::Spanned, Data, DeriveInput, Fields, GenericArgument, GenericParam,
+    Ident, PathArguments, Type, TypeParamBound, WherePredicate,
+};
+
+/// Derive conversions between [`Stor`] instantiations of a type
+///
+/// For a type generic over `S: Stor` this generates:
+/// - `to_owned_stor(&self) -> Self<Owned>`, copying the type into owned storage (requires the `stor/alloc` feature)
+/// - `into_owned_stor(self) -> Self<Owned>`, moving the type into owned storage where `S: IntoOwned` (requires the `stor/alloc` feature)
+/// - `from_owned_stor(Self<Owned>) -> Self`, moving the type from owned storage where `S: FromOwned` (requires the `stor/alloc` feature)
+/// - `as_ref_stor(&self) -> Self<Ref<'_>>`, borrowing the type as reference storage
+/// - `to_bump_stor(&self, &'b bumpalo::Bump) -> Self<Bump<'b>>`, copying the type into arena storage (requires the `stor/bumpalo` feature)
+/// - `to_buf_stor(&self, &bytes::Bytes) -> Self<Buf>`, slicing strings and bytes from a parent buffer where these are borrowed from it (requires the `stor/bytes` feature)
+/// - `to_slab_stor(&self, &'b SlabBuffer) -> Result<Self<Slab<'b>>, CapacityError>`, copying the type into a scratch buffer (requires the `stor/slab` feature)
+///
+/// Fields of `S::String`, `S::Bytes` and `S::List<T>` are converted, fields of
+/// other types generic over `S` are converted by calling their own conversion methods
+/// (so must also derive [`StorConvert`]), and all other fields are cloned or moved.
+///
+/// `as_ref_stor` is not generated for types containing lists of objects generic over `S`,
+/// as borrowing these would require allocating a new list.
+///
+/// ```
+/// use stor::{Stor, StorConvert, Owned, Ref};
+///
+/// #[derive(Debug, StorConvert)]
+/// struct Header<S: Stor> {
+///     name: S::String,
+///     value: S::Bytes,
+///     index: u32,
+/// }
+///
+/// let h = Header::<Ref> { name: "a", value: &[0xaa], index: 1 };
+/// let o: Header<Owned> = h.to_owned_stor();
+/// let r: Header<Ref> = o.as_ref_stor();
+/// assert_eq!(r.name, "a");
+/// ```
+///
+/// [`Stor`]: https://docs.rs/stor/latest/stor/trait.Stor.html
+#[proc_macro_derive(StorConvert)]
+pub fn derive_stor_convert(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match stor_convert(input) {
+        Ok(t) => t.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Derive `stor::codec::Encode` for a type
+///
+/// Fields of `S::String`, `S::Bytes` and `S::List<T>` are written as length-prefixed
+/// sequences, all other fields must implement `Encode`. Enum variants are prefixed
+/// by their index. See the `stor::codec` module for an example.
+#[proc_macro_derive(Encode)]
+pub fn derive_encode(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match codec::encode(input) {
+        Ok(t) => t.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Derive `stor::codec::Decode` for a type
+///
+/// Fields of `S::String`, `S::Bytes` and `S::List<T>` are decoded using
+/// `StorDecode`, so borrow from the input buffer where `S` is `Ref`,
+/// all other fields must implement `Decode`.
+#[proc_macro_derive(Decode)]
+pub fn derive_decode(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match codec::decode(input) {
+        Ok(t) => t.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Derive [`PartialEq`] between instantiations of a type over different [`Stor`] markers
+///
+/// For a type generic over `S: Stor` this implements `PartialEq<Self<S2>> for Self<S>`
+/// for any `S2`, comparing the contents of `S::String`, `S::Bytes` and `S::List<T>`
+/// fields, and comparing other fields with [`PartialEq`]. This includes `S2 = S`,
+/// so replaces `#[derive(PartialEq)]`.
+///
+/// ```
+/// use stor::{Stor, StorPartialEq, Owned, Ref};
+///
+/// #[derive(Debug, StorPartialEq)]
+/// struct Header<S: Stor> {
+///     name: S::String,
+///     value: S::Bytes,
+/// }
+///
+/// let r = Header::<Ref> { name: "a", value: &[0xaa] };
+/// let o = Header::<Owned> { name: "a".to_string(), value: vec![0xaa] };
+/// assert_eq!(r, o);
+/// ```
+///
+/// [`Stor`]: https://docs.rs/stor/latest/stor/trait.Stor.html
+#[proc_macro_derive(StorPartialEq)]
+pub fn derive_stor_partial_eq(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match cmp::stor_partial_eq(input) {
+        Ok(t) => t.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Kinds of field handled by [`StorConvert`]
+enum Kind<'a> {
+    /// `S::String` field
+    String,
+    /// `S::Bytes` field
+    Bytes,
+    /// `S::List<T>` field, with element type and whether this is generic over `S`
+    List(&'a Type, bool),
+    /// Other field generic over `S`
+    Nested,
+    /// `PhantomData` marker
+    Phantom,
+    /// Field independent of `S`
+    Plain,
+}
+
+fn stor_convert(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let stor = find_stor_param(&input).ok_or_else(|| {
+        syn::Error::new(input.ident.span(), "StorConvert requires a type parameter bounded by `Stor`")
+    })?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // Collect field conversions and bounds
+    let mut clone_bounds = vec![];
+    let mut into_bounds = vec![];
+    let mut ref_supported = true;
+
+    // Build conversions for a set of fields bound to `__N` identifiers
+    let mut convert = |fields: &Fields, bindings: &[Ident]| -> [TokenStream2; 7] {
+        let mut to_owned = vec![];
+        let mut into_owned = vec![];
+        let mut from_owned = vec![];
+        let mut as_ref = vec![];
+        let mut to_bump = vec![];
+        let mut to_slab = vec![];
+        let mut to_buf = vec![];
+
+        for (f, e) in fields.iter().zip(bindings) {
+            let ty = &f.ty;
+            let (o, i, fr, r, b, sl, bf) = match classify(ty, &stor) {
+                Kind::String => (
+                    quote!(::stor::__private::to_owned_string(#e)),
+                    quote!(<#stor as ::stor::IntoOwned>::into_owned_string(#e)),
+                    quote!(<#stor as ::stor::FromOwned>::from_owned_string(#e)),
+                    quote!(::stor::__private::as_ref_string(#e)),
+                    quote!(::stor::Bump::string_in(::stor::__private::as_ref_string(#e), bump)),
+                    quote!(slab.string_from(::stor::__private::as_ref_string(#e))?),
+                    quote!(::stor::Buf::string_in(::stor::__private::as_ref_string(#e), parent)),
+                ),
+                Kind::Bytes => (
+                    quote!(::stor::__private::to_owned_bytes(#e)),
+                    quote!(<#stor as ::stor::IntoOwned>::into_owned_bytes(#e)),
+                    quote!(<#stor as ::stor::FromOwned>::from_owned_bytes(#e)),
+                    quote!(::stor::__private::as_ref_bytes(#e)),
+                    quote!(::stor::Bump::bytes_in(::stor::__private::as_ref_bytes(#e), bump)),
+                    quote!(slab.bytes_from(::stor::__private::as_ref_bytes(#e))?),
+                    quote!(::stor::Buf::bytes_in(::stor::__private::as_ref_bytes(#e), parent)),
+                ),
+                Kind::List(t, true) => {
+                    ref_supported = false;
+                    into_bounds.push(quote!(#t: ::core::clone::Clone));
+                    (
+                        quote!(::stor::__private::to_owned_list(#e, |v: &#t| v.to_owned_stor())),
+                        quote!(<#stor as ::stor::IntoOwned>::into_owned_list(#e).into_iter().map(|v: #t| v.into_owned_stor()).collect()),
+                        quote!(<#stor as ::stor::FromOwned>::from_owned_list(#e.into_iter().map(<#t>::from_owned_stor).collect())),
+                        quote!(),
+                        quote!(::stor::BumpList::from_iter_in(::core::convert::AsRef::<[#t]>::as_ref(#e).iter().map(|v| v.to_bump_stor(bump)), bump)),
+                        quote!(::stor::__private::to_slab_list(slab, #e, |v: &#t| v.to_slab_stor(slab))?),
+                        quote!(::stor::__private::to_shared_list(#e, |v: &#t| v.to_buf_stor(parent))),
+                    )
+                }
+                Kind::List(t, false) => {
+                    clone_bounds.push(quote!(#t: ::core::clone::Clone));
+                    (
+                        quote!(::stor::__private::to_owned_list(#e, ::core::clone::Clone::clone)),
+                        quote!(<#stor as ::stor::IntoOwned>::into_owned_list(#e)),
+                        quote!(<#stor as ::stor::FromOwned>::from_owned_list(#e)),
+                        quote!(::stor::__private::as_ref_list(#e)),
+                        quote!(::stor::Bump::list_in(::core::convert::AsRef::<[#t]>::as_ref(#e), bump)),
+                        quote!(slab.list_from(::core::convert::AsRef::<[#t]>::as_ref(#e))?),
+                        quote!(::stor::__private::to_shared_list(#e, ::core::clone::Clone::clone)),
+                    )
+                }
+                Kind::Nested => (
+                    quote!(#e.to_owned_stor()),
+                    quote!(#e.into_owned_stor()),
+                    quote!(<#ty>::from_owned_stor(#e)),
+                    quote!(#e.as_ref_stor()),
+                    quote!(#e.to_bump_stor(bump)),
+                    quote!(#e.to_slab_stor(slab)?),
+                    quote!(#e.to_buf_stor(parent)),
+                ),
+                Kind::Phantom => (
+                    quote!(::core::marker::PhantomData),
+                    quote!(::core::marker::PhantomData),
+                    quote!(::core::marker::PhantomData),
+                    quote!(::core::marker::PhantomData),
+                    quote!(::core::marker::PhantomData),
+                    quote!(::core::marker::PhantomData),
+                    quote!(::core::marker::PhantomData),
+                ),
+                Kind::Plain => {
+                    clone_bounds.push(quote!(#ty: ::core::clone::Clone));
+                    (
+                        quote!(::core::clone::Clone::clone(#e)),
+                        quote!(#e),
+                        quote!(#e),
+                        quote!(::core::clone::Clone::clone(#e)),
+                        quote!(::core::clone::Clone::clone(#e)),
+                        quote!(::core::clone::Clone::clone(#e)),
+                        quote!(::core::clone::Clone::clone(#e)),
+                    )
+                }
+            };
+
+            match &f.ident {
+                Some(n) => {
+                    to_owned.push(quote!(#n: #o));
+                    into_owned.push(quote!(#n: #i));
+                    from_owned.push(quote!(#n: #fr));
+                    as_ref.push(quote!(#n: #r));
+                    to_bump.push(quote!(#n: #b));
+                    to_slab.push(quote!(#n: #sl));
+                    to_buf.push(quote!(#n: #bf));
+                }
+                None => {
+                    to_owned.push(o);
+                    into_owned.push(i);
+                    from_owned.push(fr);
+                    as_ref.push(r);
+                    to_bump.push(b);
+                    to_slab.push(sl);
+                    to_buf.push(bf);
+                }
+            }
+        }
+
+        [to_owned, into_owned, from_owned, as_ref, to_bump, to_slab, to_buf].map(|v| wrap(fields, v))
+    };
+
+    // Match each variant (or the struct itself) and rebuild with converted fields
+    let mut arms: [Vec<TokenStream2>; 7] = Default::default();
+    for (path, fields) in variants(&input, "StorConvert")? {
+        let bindings = bindings(fields);
+        let pattern = pattern(fields, &bindings);
+
+        for (arm, body) in arms.iter_mut().zip(convert(fields, &bindings)) {
+            arm.push(quote!(#path #pattern => #path #body));
+        }
+    }
+
+    let [to_owned_body, into_owned_body, from_owned_body, as_ref_body, to_bump_body, to_slab_body, to_buf_body] = {
+        let mut values = [quote!(self), quote!(self), quote!(owned), quote!(self), quote!(self), quote!(self), quote!(self)].into_iter();
+        arms.map(|a| {
+            let v = values.next().unwrap();
+            quote!(match #v { #(#a,)* })
+        })
+    };
+
+    // Build target types with the storage parameter substituted
+    let lifetime = syn::Lifetime::new("'__stor", Span::call_site());
+    let owned_ty = substitute(&input, &stor, quote!(::stor::Owned));
+    let ref_ty = substitute(&input, &stor, quote!(::stor::Ref<#lifetime>));
+    let bump_ty = substitute(&input, &stor, quote!(::stor::Bump<#lifetime>));
+    let slab_ty = substitute(&input, &stor, quote!(::stor::Slab<#lifetime>));
+    let buf_ty = substitute(&input, &stor, quote!(::stor::Buf));
+
+    let predicates = where_clause.map(|w| {
+        let p = w.predicates.iter();
+        quote!(#(#p,)*)
+    });
+    let bounds = quote!(where #predicates #(#clone_bounds,)*);
+
+    let as_ref_impl = match ref_supported {
+        true => quote! {
+            impl #impl_generics #name #ty_generics #bounds {
+                /// Borrow this object as [`stor::Ref`] storage
+                pub fn as_ref_stor<#lifetime>(&#lifetime self) -> #ref_ty {
+                    #as_ref_body
+                }
+            }
+        },
+        false => quote!(),
+    };
+
+    Ok(quote! {
+        ::stor::__if_alloc! {
+            impl #impl_generics #name #ty_generics #bounds {
+                /// Copy this object into [`stor::Owned`] storage
+                pub fn to_owned_stor(&self) -> #owned_ty {
+                    #to_owned_body
+                }
+            }
+
+            impl #impl_generics #name #ty_generics #bounds #stor: ::stor::IntoOwned, #(#into_bounds,)* {
+                /// Convert this object into [`stor::Owned`] storage, moving owned contents
+                pub fn into_owned_stor(self) -> #owned_ty {
+                    #into_owned_body
+                }
+            }
+
+            impl #impl_generics #name #ty_generics #bounds #stor: ::stor::FromOwned, {
+                /// Convert an object in [`stor::Owned`] storage into this storage type, moving owned contents
+                pub fn from_owned_stor(owned: #owned_ty) -> Self {
+                    #from_owned_body
+                }
+            }
+        }
+
+        #as_ref_impl
+
+        ::stor::__if_bumpalo! {
+            impl #impl_generics #name #ty_generics #bounds {
+                /// Copy this object into [`stor::Bump`] storage allocated from the provided arena
+                pub fn to_bump_stor<#lifetime>(&self, bump: &#lifetime ::stor::bumpalo::Bump) -> #bump_ty {
+                    #to_bump_body
+                }
+            }
+        }
+
+        ::stor::__if_bytes! {
+            impl #impl_generics #name #ty_generics #bounds {
+                /// Convert this object into [`stor::Buf`] storage, slicing strings and bytes borrowed from the parent buffer
+                pub fn to_buf_stor(&self, parent: &::stor::bytes::Bytes) -> #buf_ty {
+                    #to_buf_body
+                }
+            }
+        }
+
+        ::stor::__if_slab! {
+            impl #impl_generics #name #ty_generics #bounds {
+                /// Copy this object into [`stor::Slab`] storage allocated from the provided buffer
+                pub fn to_slab_stor<#lifetime>(&self, slab: &#lifetime ::stor::SlabBuffer<'_>) -> ::core::result::Result<#slab_ty, ::stor::CapacityError> {
+                    ::core::result::Result::Ok(#to_slab_body)
+                }
+            }
+        }
+    })
+}
+
+/// Fetch the constructor path and fields for each variant (or the struct itself)
+fn variants<'a>(input: &'a DeriveInput, derive: &str) -> syn::Result<Vec<(TokenStream2, &'a Fields)>> {
+    let name = &input.ident;
+
+    match &input.data {
+        Data::Struct(s) => Ok(vec![(quote!(#name), &s.fields)]),
+        Data::Enum(e) => Ok(e.variants.iter().map(|v| {
+            let variant = &v.ident;
+            (quote!(#name::#variant), &v.fields)
+        }).collect()),
+        Data::Union(u) => {
+            Err(syn::Error::new(u.union_token.span(), format!("{derive} does not support unions")))
+        }
+    }
+}
+
+/// Generate `__N` identifiers for binding each field
+fn bindings(fields: &Fields) -> Vec<Ident> {
+    (0..fields.len()).map(|i| format_ident!("__{}", i)).collect()
+}
+
+/// Build a pattern binding each field to the provided identifiers
+fn pattern(fields: &Fields, bindings: &[Ident]) -> TokenStream2 {
+    match fields {
+        Fields::Named(n) => {
+            let names = n.named.iter().map(|f| f.ident.as_ref().unwrap());
+            quote!({ #(#names: #bindings),* })
+        }
+        _ => wrap(fields, bindings.iter().map(|b| quote!(#b)).collect()),
+    }
+}
+
+/// Wrap field expressions in the delimiters for the provided fields
+fn wrap(fields: &Fields, v: Vec<TokenStream2>) -> TokenStream2 {
+    match fields {
+        Fields::Named(_) => quote!({ #(#v),* }),
+        Fields::Unnamed(_) => quote!(( #(#v),* )),
+        Fields::Unit => quote!(),
+    }
+}
+
+/// Locate the type parameter bounded by `Stor`
+fn find_stor_param(input: &DeriveInput) -> Option<Ident> {
+    let is_stor = |b: &TypeParamBound| match b {
+        TypeParamBound::Trait(t) => t.path.segments.last().map(|s| s.ident == "Stor").unwrap_or(false),
+        _ => false,
+    };
+
+    for p in input.generics.type_params() {
+        if p.bounds.iter().any(is_stor) {
+            return Some(p.ident.clone());
+        }
+    }
+
+    if let Some(w) = &input.generics.where_clause {
+        for p in &w.predicates {
+            if let WherePredicate::Type(t) = p {
+                if let Type::Path(tp) = &t.bounded_ty {
+                    if let Some(i) = tp.path.get_ident() {
+                        if t.bounds.iter().any(is_stor) {
+                            return Some(i.clone());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Classify a field type by its use of the storage parameter
+fn classify<'a>(ty: &'a Type, stor: &Ident) -> Kind<'a> {
+    if !mentions(ty, stor) {
+        return Kind::Plain;
+    }
+
+    let p = match ty {
+        Type::Path(p) => p,
+        _ => return Kind::Nested,
+    };
+
+    // Match `S::Name` or `<S as Stor>::Name`
+    let assoc = match &p.qself {
+        None if p.path.segments.len() == 2 && p.path.segments[0].ident == *stor => Some(&p.path.segments[1]),
+        Some(q) if mentions(&q.ty, stor) => p.path.segments.last(),
+        _ => None,
+    };
+
+    if let Some(a) = assoc {
+        match a.ident.to_string().as_str() {
+            "String" => return Kind::String,
+            "Bytes" => return Kind::Bytes,
+            "List" => {
+                if let PathArguments::AngleBracketed(args) = &a.arguments {
+                    if let Some(GenericArgument::Type(t)) = args.args.first() {
+                        return Kind::List(t, mentions(t, stor));
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    match p.path.segments.last() {
+        Some(s) if s.ident == "PhantomData" => Kind::Phantom,
+        _ => Kind::Nested,
+    }
+}
+
+/// Check whether a type refers to the provided identifier
+fn mentions(ty: &Type, ident: &Ident) -> bool {
+    fn walk(t: TokenStream2, ident: &Ident) -> bool {
+        t.into_iter().any(|t| match t {
+            TokenTree::Ident(i) => i == *ident,
+            TokenTree::Group(g) => walk(g.stream(), ident),
+            _ => false,
+        })
+    }
+
+    walk(quote!(#ty), ident)
+}
+
+/// Replace an identifier throughout the provided tokens
+fn replace(t: TokenStream2, ident: &Ident, with: &Ident) -> TokenStream2 {
+    t.into_iter().map(|t| match t {
+        TokenTree::Ident(i) if i == *ident => TokenTree::Ident(with.clone()),
+        TokenTree::Group(g) => {
+            let mut n = proc_macro2::Group::new(g.delimiter(), replace(g.stream(), ident, with));
+            n.set_span(g.span());
+            TokenTree::Group(n)
+        }
+        t => t,
+    }).collect()
+}
+
+/// Build the type of the input with the storage parameter replaced
+fn substitute(input: &DeriveInput, stor: &Ident, with: TokenStream2) -> TokenStream2 {
+    let name = &input.ident;
+
+    let args = input.generics.params.iter().map(|p| match p {
+        GenericParam::Type(t) if t.ident == *stor => with.clone(),
+        GenericParam::Type(t) => {
+            let i = &t.ident;
+            quote!(#i)
+        }
+        GenericParam::Lifetime(l) => {
+            let l = &l.lifetime;
+            quote!(#l)
+        }
+        GenericParam::Const(c) => {
+            let i = &c.ident;
+            quote!(#i)
+        }
+    });
+
+    quote!(#name<#(#args),*>)
+}
@@ -0,0 +1,64 @@
+use stor::{Stor, StorPartialEq, Owned, Ref, RefList, Bounded, BoundedVec, Const, ConstString};
+
+#[derive(Debug, StorPartialEq)]
+struct Header<S: Stor> {
+    name: S::String,
+    value: S::Bytes,
+    index: u32,
+}
+
+#[derive(Debug, StorPartialEq)]
+enum Something<S: Stor> {
+    List(S::List<u32>),
+    Header(Header<S>),
+    Headers { headers: S::List<Header<S>> },
+    Str(S::String),
+    Empty,
+}
+
+#[derive(Debug, StorPartialEq)]
+struct Wrapper<'a, T: Clone, S> where S: Stor {
+    inner: &'a T,
+    name: S::String,
+}
+
+#[test]
+fn struct_cross_eq() {
+    let r = Header::<Ref> { name: "a", value: &[0xaa, 0xbb], index: 4 };
+    let o = Header::<Owned> { name: "a".to_string(), value: vec![0xaa, 0xbb], index: 4 };
+    let c = Header::<Const<2>> { name: ConstString::from_str_const("a"), value: [0xaa, 0xbb], index: 4 };
+
+    assert_eq!(r, o);
+    assert_eq!(o, c);
+    assert_eq!(r, r);
+
+    let o2 = Header::<Owned> { index: 5, ..o };
+    assert_ne!(r, o2);
+}
+
+#[test]
+fn enum_cross_eq() {
+    let h = [Header::<Ref> { name: "a", value: &[], index: 1 }];
+    let r = Something::<Ref>::Headers { headers: RefList::new(&h) };
+    let o = Something::<Owned>::Headers { headers: vec![Header { name: "a".to_string(), value: vec![], index: 1 }] };
+    assert_eq!(r, o);
+
+    let r = Something::<Ref>::Header(Header { name: "b", value: &[1], index: 2 });
+    assert_eq!(r, Something::<Owned>::Header(Header { name: "b".to_string(), value: vec![1], index: 2 }));
+    assert_ne!(r, Something::<Owned>::Header(Header { name: "c".to_string(), value: vec![1], index: 2 }));
+
+    let r = Something::<Ref>::List(RefList::new(&[1, 2]));
+    assert_eq!(r, Something::<Bounded<2>>::List(BoundedVec::from([1, 2])));
+
+    assert_ne!(r, Something::<Owned>::List(vec![1]));
+    assert_ne!(r, Something::<Owned>::Str("a".to_string()));
+    assert_eq!(Something::<Ref>::Empty, Something::<Owned>::Empty);
+}
+
+#[test]
+fn generics_cross_eq() {
+    let v = 3u8;
+    let r = Wrapper::<u8, Ref> { inner: &v, name: "a" };
+    let o = Wrapper::<u8, Owned> { inner: &v, name: "a".to_string() };
+    assert_eq!(r, o);
+}
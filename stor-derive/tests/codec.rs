@@ -0,0 +1,89 @@
+use stor::{Stor, Owned, Ref, Heapless};
+use stor::StorError;
+use stor::codec::{self, CodecError, Decode, Encode};
+
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+struct Header<S: Stor> {
+    name: S::String,
+    value: S::Bytes,
+    index: u32,
+}
+
+#[derive(Debug, Encode, Decode)]
+enum Something<S: Stor> {
+    List(S::List<u32>),
+    Header(Header<S>),
+    Headers { headers: S::List<Header<S>> },
+    Flag(Option<bool>),
+    Empty,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Plain(u8, i64);
+
+fn encode<T: Encode>(v: &T) -> Vec<u8> {
+    let mut buff = vec![0u8; codec::encoded_len(v)];
+    let n = codec::encode(v, &mut buff).unwrap();
+    assert_eq!(n, buff.len());
+    buff
+}
+
+#[test]
+fn struct_borrow() {
+    let h = Header::<Ref> { name: "abc", value: &[0xaa, 0xbb], index: 4 };
+    let buff = encode(&h);
+
+    let (r, n) = codec::decode::<Header<Ref>>(&buff).unwrap();
+    assert_eq!(n, buff.len());
+    assert_eq!(r, h);
+
+    // Strings and bytes borrow from the buffer
+    assert!(core::ptr::eq(r.value.as_ptr(), buff[5..].as_ptr()));
+}
+
+#[test]
+fn struct_capacity() {
+    let h = Header::<Ref> { name: "abc", value: &[0xaa, 0xbb], index: 4 };
+    let buff = encode(&h);
+
+    let (v, _) = codec::decode::<Header<Heapless<3>>>(&buff).unwrap();
+    assert_eq!(v.name, "abc");
+
+    assert_eq!(codec::decode::<Header<Heapless<2>>>(&buff).unwrap_err(), CodecError::Stor(StorError::CapacityOverflow));
+    assert_eq!(codec::decode::<Header<Ref>>(&buff[..4]).unwrap_err(), CodecError::BufferTooShort);
+}
+
+#[test]
+fn enum_round_trip() {
+    let values = [
+        Something::<Owned>::List(vec![1, 2]),
+        Something::Header(Header { name: "a".to_string(), value: vec![1], index: 2 }),
+        Something::Headers { headers: vec![Header { name: "b".to_string(), value: vec![], index: 3 }] },
+        Something::Flag(Some(false)),
+        Something::Empty,
+    ];
+
+    for v in values {
+        let buff = encode(&v);
+        let (d, _) = codec::decode::<Something<Owned>>(&buff).unwrap();
+        assert_eq!(encode(&d), buff);
+    }
+
+    // Lists cannot be borrowed, but other variants can
+    let buff = encode(&Something::<Owned>::List(vec![1]));
+    assert_eq!(codec::decode::<Something<Ref>>(&buff).unwrap_err(), CodecError::Unsupported);
+
+    let buff = encode(&Something::<Owned>::Flag(None));
+    assert!(matches!(codec::decode::<Something<Ref>>(&buff).unwrap().0, Something::Flag(None)));
+
+    // Unknown variant index
+    assert_eq!(codec::decode::<Something<Owned>>(&[5]).unwrap_err(), CodecError::InvalidValue);
+}
+
+#[test]
+fn plain_types() {
+    let p = Plain(1, -2);
+    let buff = encode(&p);
+    assert_eq!(buff.len(), 9);
+    assert_eq!(codec::decode::<Plain>(&buff).unwrap().0, p);
+}
@@ -0,0 +1,159 @@
+use stor::{Stor, StorConvert, Owned, Ref, RefList, Cow, CowList, Boxed, SlabBuffer, CapacityError};
+
+#[derive(Debug, Clone, PartialEq, StorConvert)]
+struct Header<S: Stor> {
+    name: S::String,
+    value: S::Bytes,
+    index: u32,
+}
+
+#[derive(Debug, StorConvert)]
+enum Something<S: Stor> {
+    List(S::List<u32>),
+    Header(Header<S>),
+    Headers { headers: S::List<Header<S>> },
+    Str(S::String),
+    Empty,
+}
+
+#[test]
+fn struct_round_trip() {
+    let r = Header::<Ref> { name: "a", value: &[0xaa, 0xbb], index: 4 };
+
+    let o = r.to_owned_stor();
+    assert_eq!(o, Header::<Owned> { name: "a".to_string(), value: vec![0xaa, 0xbb], index: 4 });
+
+    assert_eq!(o.as_ref_stor(), r);
+}
+
+#[test]
+fn enum_to_owned() {
+    let h = Header::<Ref> { name: "a", value: &[0xaa], index: 1 };
+    let headers = [h.clone(), h.clone()];
+
+    let o = Something::<Ref>::Headers{ headers: RefList::new(&headers) }.to_owned_stor();
+    match o {
+        Something::Headers{ headers } => assert_eq!(headers, vec![h.to_owned_stor(), h.to_owned_stor()]),
+        _ => panic!("unexpected variant"),
+    }
+
+    let o = Something::<Ref>::List(RefList::new(&[1, 2, 3])).to_owned_stor();
+    assert!(matches!(o, Something::List(l) if l == [1, 2, 3]));
+
+    let o = Something::<Ref>::Header(h.clone()).to_owned_stor();
+    assert!(matches!(o, Something::Header(v) if v.as_ref_stor() == h));
+
+    let o = Something::<Ref>::Str("abc").to_owned_stor();
+    assert!(matches!(o, Something::Str(s) if s == "abc"));
+
+    assert!(matches!(Something::<Ref>::Empty.to_owned_stor(), Something::Empty));
+}
+
+#[derive(Debug, StorConvert)]
+struct Tuple<'a, S: Stor, T: core::fmt::Debug> (S::List<T>, &'a str);
+
+#[test]
+fn tuple_generics() {
+    let t = Tuple::<Owned, u8>(vec![1, 2], "x");
+    let r = t.as_ref_stor();
+    assert_eq!(r.0, [1, 2]);
+    assert_eq!(r.1, "x");
+}
+
+#[test]
+fn cow_into_owned() {
+    let h = Header::<Cow> { name: "a".into(), value: vec![0xaa].into(), index: 1 };
+    let headers = [h.clone()];
+
+    let o = Something::<Cow>::Headers{ headers: CowList::from(&headers[..]) }.into_owned_stor();
+    match o {
+        Something::Headers{ headers } => assert_eq!(headers, vec![h.to_owned_stor()]),
+        _ => panic!("unexpected variant"),
+    }
+
+    let o = Something::<Cow>::Str("abc".into()).into_owned_stor();
+    assert!(matches!(o, Something::Str(s) if s == "abc"));
+}
+
+#[test]
+fn boxed_from_owned() {
+    let mut name = String::with_capacity(64);
+    name.push_str("abc");
+
+    let h = Header::<Owned> { name, value: vec![0xaa], index: 1 };
+    let o = Something::<Owned>::Headers{ headers: vec![h.clone()] };
+
+    let b = Something::<Boxed>::from_owned_stor(o);
+    match &b {
+        Something::Headers{ headers } => {
+            assert_eq!(headers.len(), 1);
+            assert_eq!(&*headers[0].name, "abc");
+        },
+        _ => panic!("unexpected variant"),
+    }
+
+    let o = b.into_owned_stor();
+    assert!(matches!(o, Something::Headers{ headers } if headers == [h]));
+}
+
+#[test]
+fn ref_to_bump() {
+    let arena = bumpalo::Bump::new();
+
+    let h = Header::<Ref> { name: "a", value: &[0xaa], index: 1 };
+    let b = h.to_bump_stor(&arena);
+    assert_eq!(b.name, "a");
+    assert_eq!(b.value, [0xaa]);
+    assert_eq!(b.to_owned_stor(), h.to_owned_stor());
+
+    let headers = [h.clone(), h.clone()];
+    let b = Something::<Ref>::Headers{ headers: RefList::new(&headers) }.to_bump_stor(&arena);
+    match b {
+        Something::Headers{ headers } => assert_eq!(headers.len(), 2),
+        _ => panic!("unexpected variant"),
+    }
+
+    let b = Something::<Ref>::List(RefList::new(&[1, 2, 3])).to_bump_stor(&arena);
+    assert!(matches!(b, Something::List(l) if l == [1, 2, 3]));
+}
+
+#[test]
+fn ref_to_slab() {
+    let mut scratch = [core::mem::MaybeUninit::<u64>::uninit(); 32];
+    let slab = SlabBuffer::from_uninit(&mut scratch);
+
+    let h = Header::<Ref> { name: "a", value: &[0xaa], index: 1 };
+    let s = h.to_slab_stor(&slab).unwrap();
+    assert_eq!(s.name, "a");
+    assert_eq!(s.value, [0xaa]);
+    assert_eq!(s.to_owned_stor(), h.to_owned_stor());
+
+    let headers = [h.clone(), Header { name: "b", value: &[0xbb, 0xcc], index: 2 }];
+    let s = Something::<Ref>::Headers{ headers: RefList::new(&headers) }.to_slab_stor(&slab).unwrap();
+    match s {
+        Something::Headers{ headers } => assert_eq!(headers[1].value, [0xbb, 0xcc]),
+        _ => panic!("unexpected variant"),
+    }
+
+    // Objects exceeding the remaining buffer fail to convert
+    let r = Something::<Ref>::List(RefList::new(&[0; 64])).to_slab_stor(&slab);
+    assert!(matches!(r, Err(CapacityError)));
+}
+
+#[test]
+fn ref_to_buf() {
+    let parent = bytes::Bytes::from_static(b"a\xaa\xbb");
+
+    let h = Header::<Ref> { name: std::str::from_utf8(&parent[..1]).unwrap(), value: &parent[1..], index: 1 };
+    let b = h.to_buf_stor(&parent);
+    assert_eq!(b.name, "a");
+    assert_eq!(b.value.as_ptr(), parent[1..].as_ptr());
+    assert_eq!(b.to_owned_stor(), h.to_owned_stor());
+
+    let headers = [h.clone(), Header { name: "b", value: &[0xcc], index: 2 }];
+    let b = Something::<Ref>::Headers{ headers: RefList::new(&headers) }.to_buf_stor(&parent);
+    match b {
+        Something::Headers{ headers } => assert_eq!(headers[1].value, [0xcc][..]),
+        _ => panic!("unexpected variant"),
+    }
+}
@@ -0,0 +1,105 @@
+#![cfg(all(feature = "serde", feature = "alloc", feature = "heapless"))]
+
+use serde::{Deserialize, Serialize};
+use stor::{Stor, Owned, Ref, Bounded, Heapless};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Something<S: Stor> {
+    List(S::List<u32>),
+    Headers(S::List<Header<S>>),
+    Str(S::String),
+    Bytes(S::Bytes),
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Header<S: Stor> {
+    name: S::String,
+    value: S::Bytes,
+}
+
+#[test]
+fn owned_round_trip() {
+    let s = Something::<Owned>::Headers(vec![Header{ name: "a".to_string(), value: vec![0xaa] }]);
+
+    let j = serde_json::to_string(&s).unwrap();
+    assert_eq!(j, r#"{"Headers":[{"name":"a","value":[170]}]}"#);
+
+    let d: Something<Owned> = serde_json::from_str(&j).unwrap();
+    assert_eq!(d, s);
+}
+
+#[test]
+fn ref_borrows() {
+    let j = r#"{"name":"abc","value":[1,2]}"#;
+
+    let h: Header<Owned> = serde_json::from_str(j).unwrap();
+    let r = serde_json::to_string(&Header::<Ref>{ name: &h.name, value: &h.value }).unwrap();
+    assert_eq!(r, j);
+
+    let j = r#"{"Str":"abc"}"#;
+    let s: Something<Ref> = serde_json::from_str(j).unwrap();
+    assert_eq!(s, Something::Str("abc"));
+
+    let j = r#"{"List":[1,2]}"#;
+    assert!(serde_json::from_str::<Something<Ref>>(j).is_err());
+}
+
+#[test]
+fn bounded_capacity() {
+    let j = r#"{"List":[1,2,3]}"#;
+
+    let s: Something<Bounded<3>> = serde_json::from_str(j).unwrap();
+    assert!(matches!(s, Something::List(l) if l == [1, 2, 3]));
+
+    assert!(serde_json::from_str::<Something<Bounded<2>>>(j).is_err());
+    assert!(serde_json::from_str::<Something<Heapless<2>>>(j).is_err());
+
+    let j = r#"{"Str":"abc"}"#;
+    assert!(serde_json::from_str::<Something<Bounded<3>>>(j).is_ok());
+    assert!(serde_json::from_str::<Something<Bounded<2>>>(j).is_err());
+}
+
+#[test]
+#[cfg(all(feature = "arrayvec", feature = "tinyvec"))]
+fn fixed_capacity_backends() {
+    let j = r#"{"Headers":[{"name":"abc","value":[1,2,3]}]}"#;
+
+    let s: Something<stor::ArrayVec<3>> = serde_json::from_str(j).unwrap();
+    assert_eq!(serde_json::to_string(&s).unwrap(), j);
+    assert!(serde_json::from_str::<Something<stor::ArrayVec<2>>>(j).is_err());
+
+    let s: Something<stor::TinyVec<3>> = serde_json::from_str(j).unwrap();
+    assert_eq!(serde_json::to_string(&s).unwrap(), j);
+    assert!(serde_json::from_str::<Something<stor::TinyVec<2>>>(j).is_err());
+
+    let j = r#"{"Bytes":[1,2,3]}"#;
+    assert!(serde_json::from_str::<Something<stor::TinyVec<3>>>(j).is_ok());
+    assert!(serde_json::from_str::<Something<stor::TinyVec<2>>>(j).is_err());
+}
+
+#[test]
+#[cfg(feature = "smallvec")]
+fn inline_round_trip() {
+    let j = r#"{"Headers":[{"name":"a","value":[170]},{"name":"abcdef","value":[1,2,3,4,5]}]}"#;
+
+    let s: Something<stor::Inline<4>> = serde_json::from_str(j).unwrap();
+    assert_eq!(serde_json::to_string(&s).unwrap(), j);
+}
+
+#[test]
+#[cfg(feature = "smol_str")]
+fn smol_strings_round_trip() {
+    let j = r#"{"Headers":[{"name":"a","value":[170]}]}"#;
+
+    let s: Something<stor::Smol> = serde_json::from_str(j).unwrap();
+    assert_eq!(serde_json::to_string(&s).unwrap(), j);
+}
+
+#[test]
+#[cfg(feature = "compact_str")]
+fn compact_strings_round_trip() {
+    let j = r#"{"Headers":[{"name":"a","value":[170]}]}"#;
+
+    let s: Something<stor::Compact> = serde_json::from_str(j).unwrap();
+    assert_eq!(serde_json::to_string(&s).unwrap(), j);
+}